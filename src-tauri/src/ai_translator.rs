@@ -1,7 +1,10 @@
+use crate::error::AppError;
 use crate::models::AIPlatform;
+use crate::network::NetworkOptions;
+use crate::sanitizer::sanitize_html;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use tokio::sync::mpsc;
 
 /// AI翻译配置
@@ -106,7 +109,8 @@ fn safe_truncate(s: &str, max_len: usize) -> String {
 /// AI翻译器结构体
 #[derive(Debug, Clone)]
 pub struct AITranslator {
-    client: Client,
+    /// HTTP客户端，代理或证书配置无效时为错误信息
+    client: Result<Client, String>,
     config: TranslatorConfig,
     default_platform: Option<AIPlatform>,
 }
@@ -115,7 +119,7 @@ impl AITranslator {
     /// 创建新的AI翻译器
     pub fn new(config: Option<TranslatorConfig>) -> Self {
         Self {
-            client: Client::builder()
+            client: Ok(Client::builder()
                 .timeout(std::time::Duration::from_secs(
                     config
                         .as_ref()
@@ -123,12 +127,34 @@ impl AITranslator {
                         .timeout,
                 ))
                 .build()
-                .unwrap(),
+                .unwrap()),
             config: config.unwrap_or_default(),
             default_platform: None,
         }
     }
 
+    /// 设置代理和CA证书，重新创建HTTP客户端
+    pub fn with_network(mut self, network: &NetworkOptions) -> Self {
+        let builder =
+            Client::builder().timeout(std::time::Duration::from_secs(self.config.timeout));
+        self.client = network
+            .apply(builder, true)
+            .and_then(|builder| builder.build().map_err(AppError::from))
+            .map_err(|e| {
+                // 代理或证书配置无效时不发送请求，避免绕过用户设置的代理或证书直接连接
+                eprintln!("AI接口应用网络设置失败: {}，修正配置前不会调用AI接口", e);
+                e.to_string()
+            });
+        self
+    }
+
+    /// 获取HTTP客户端，网络设置无效时返回错误
+    fn client(&self) -> Result<&Client, AppError> {
+        self.client
+            .as_ref()
+            .map_err(|e| AppError::Ai(format!("网络设置无效: {}", e)))
+    }
+
     /// 设置默认AI平台（返回新的实例，不影响原实例）
     pub fn with_default_platform(mut self, platform: Option<AIPlatform>) -> Self {
         self.default_platform = platform;
@@ -146,7 +172,7 @@ impl AITranslator {
         text: &str,
         target_language: &str,
         source_language: Option<&str>,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> Result<String, AppError> {
         eprintln!("[AI] ===== 开始翻译文本 =====");
         eprintln!(
             "[AI] 文本长度: {}, 首50字符: {}...",
@@ -161,7 +187,9 @@ impl AITranslator {
         let platform = self.get_default_platform().clone();
         if platform.is_none() {
             eprintln!("[AI] 错误: 没有配置默认AI平台");
-            return Err("No default AI platform configured".into());
+            return Err(AppError::Ai(
+                "No default AI platform configured".to_string(),
+            ));
         }
         let platform = platform.unwrap();
         eprintln!("[AI] 成功获取默认AI平台: {}", platform.name);
@@ -214,7 +242,7 @@ impl AITranslator {
         eprintln!("[AI] 开始发送请求到API...");
         eprintln!("[AI] API URL: {}", platform.api_url);
         let response = match self
-            .client
+            .client()?
             .post(&platform.api_url)
             .header("Authorization", format!("Bearer {}", platform.api_key))
             .header("Content-Type", "application/json")
@@ -272,7 +300,7 @@ impl AITranslator {
         // 检查是否有错误信息
         if let Some(error) = response.get("error") {
             eprintln!("[AI] API返回错误: {:?}", error);
-            return Err(format!("API Error: {:?}", error).into());
+            return Err(AppError::Ai(format!("API Error: {:?}", error)));
         }
 
         // 提取翻译结果
@@ -300,7 +328,9 @@ impl AITranslator {
             None => {
                 eprintln!("[AI] 提取翻译结果失败");
                 eprintln!("[AI] 响应结构: {:?}", response);
-                return Err("Failed to extract translated text".into());
+                return Err(AppError::Ai(
+                    "Failed to extract translated text".to_string(),
+                ));
             }
         };
 
@@ -314,7 +344,7 @@ impl AITranslator {
         title: &str,
         content: &str,
         target_language: &str,
-    ) -> Result<(String, String), Box<dyn std::error::Error>> {
+    ) -> Result<(String, String), AppError> {
         eprintln!("[AI] 开始翻译RSS文章标题和内容...");
 
         // 翻译标题
@@ -338,6 +368,8 @@ impl AITranslator {
         let translated_content = self
             .translate_text(&truncated_content, target_language, None)
             .await?;
+        // AI返回的HTML同样需要清理，避免原文中的脚本被翻译后保留下来
+        let translated_content = sanitize_html(&translated_content);
         // 安全地获取前100个字符，避免字节索引问题
         let preview = translated_content.chars().take(100).collect::<String>();
         eprintln!("[AI] 文章内容翻译完成: {}...", preview);
@@ -362,11 +394,13 @@ impl AITranslator {
         max_tokens: Option<u32>,
         temperature: Option<f32>,
         tx: mpsc::Sender<String>,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<(), AppError> {
         let platform = self.get_default_platform().clone();
         if platform.is_none() {
             eprintln!("[AI] 错误: 没有配置默认AI平台");
-            return Err("No default AI platform configured".into());
+            return Err(AppError::Ai(
+                "No default AI platform configured".to_string(),
+            ));
         }
         let platform = platform.unwrap();
         eprintln!("[AI] 开始流式聊天请求，平台: {}", platform.name);
@@ -380,7 +414,7 @@ impl AITranslator {
         };
 
         let response = self
-            .client
+            .client()?
             .post(&platform.api_url)
             .header("Authorization", format!("Bearer {}", platform.api_key))
             .header("Content-Type", "application/json")
@@ -418,7 +452,7 @@ impl AITranslator {
                     response_text.clone()
                 };
 
-            return Err(format!("API错误: {}", error_message).into());
+            return Err(AppError::Ai(format!("API错误: {}", error_message)));
         }
 
         let mut stream = response.bytes_stream();
@@ -451,7 +485,9 @@ impl AITranslator {
                 // 如果缓冲区已满，尝试清理已处理的数据或报错
                 if buffer.len() > MAX_BUFFER_SIZE {
                     eprintln!("[AI] 错误: 缓冲区已超过最大限制，无法继续处理更多数据");
-                    return Err("Buffer overflow: Maximum buffer size exceeded".into());
+                    return Err(AppError::Ai(
+                        "Buffer overflow: Maximum buffer size exceeded".to_string(),
+                    ));
                 }
             }
 
@@ -554,10 +590,12 @@ impl AITranslator {
         messages: Vec<ChatMessage>,
         max_tokens: Option<u32>,
         temperature: Option<f32>,
-    ) -> Result<String, Box<dyn std::error::Error>> {
+    ) -> Result<String, AppError> {
         let platform = self.get_default_platform().clone();
         if platform.is_none() {
-            return Err("No default AI platform configured".into());
+            return Err(AppError::Ai(
+                "No default AI platform configured".to_string(),
+            ));
         }
         let platform = platform.unwrap();
 
@@ -572,7 +610,7 @@ impl AITranslator {
 
         // 发送请求
         let response = self
-            .client
+            .client()?
             .post(&platform.api_url)
             .header("Authorization", format!("Bearer {}", platform.api_key))
             .header("Content-Type", "application/json")
@@ -610,7 +648,7 @@ impl AITranslator {
                     response_text.clone()
                 };
 
-            return Err(format!("API错误: {}", error_message).into());
+            return Err(AppError::Ai(format!("API错误: {}", error_message)));
         }
 
         // 解析响应
@@ -633,20 +671,27 @@ impl AITranslator {
 
 /// AI翻译器单例
 pub struct AITranslatorSingleton {
-    translator: Arc<AITranslator>,
+    translator: RwLock<Arc<AITranslator>>,
 }
 
 impl AITranslatorSingleton {
     /// 创建单例
     pub fn new() -> Self {
         Self {
-            translator: Arc::new(AITranslator::new(None)),
+            translator: RwLock::new(Arc::new(AITranslator::new(None))),
         }
     }
 
     /// 获取翻译器实例（返回克隆副本，支持并行操作）
     pub async fn get_translator(&self) -> AITranslator {
-        (*self.translator).clone()
+        let translator = self.translator.read().unwrap_or_else(|e| e.into_inner());
+        (**translator).clone()
+    }
+
+    /// 应用代理和CA证书设置，之后获取的翻译器实例均使用新设置
+    pub fn configure_network(&self, network: &NetworkOptions) {
+        let mut translator = self.translator.write().unwrap_or_else(|e| e.into_inner());
+        *translator = Arc::new((**translator).clone().with_network(network));
     }
 }
 
@@ -0,0 +1,358 @@
+use crate::models::content_hash;
+use url::Url;
+
+/// 默认去除的跟踪参数
+///
+/// 规则格式：
+/// - `name`：去除该参数
+/// - `prefix*`：去除以prefix开头的参数
+/// - `name=value`：仅在参数值等于value时去除（例如from=rss）
+/// - `host:rule`：仅对该域名及其子域名生效
+const DEFAULT_STRIP_RULES: &[&str] = &[
+    "utm_*",
+    "fbclid",
+    "gclid",
+    "gclsrc",
+    "dclid",
+    "gbraid",
+    "wbraid",
+    "msclkid",
+    "yclid",
+    "twclid",
+    "igshid",
+    "mc_cid",
+    "mc_eid",
+    "_hsenc",
+    "_hsmi",
+    "__hstc",
+    "__hssc",
+    "__hsfp",
+    "mkt_tok",
+    "vero_id",
+    "oly_anon_id",
+    "oly_enc_id",
+    "wt_mc",
+    "xtor",
+    "spm",
+    "scm",
+    "from=rss",
+    "from=feed",
+    "ref=rss",
+    "ref=feed",
+    "source=rss",
+    "source=feed",
+    "share_source",
+    "share_medium",
+    "youtube.com:feature",
+    "twitter.com:ref_src",
+    "x.com:ref_src",
+];
+
+/// 规范化方式的版本，修改canonicalize的处理步骤时增加，使已保存的文章链接按新方式重写
+const CANONICALIZE_VERSION: u32 = 1;
+
+/// 单条参数匹配规则
+#[derive(Debug, Clone, PartialEq)]
+struct ParamRule {
+    /// 限定的域名，为None时对所有域名生效
+    host: Option<String>,
+    /// 参数名，prefix为true时按前缀匹配
+    name: String,
+    prefix: bool,
+    /// 限定的参数值
+    value: Option<String>,
+}
+
+impl ParamRule {
+    /// 解析规则字符串，格式见DEFAULT_STRIP_RULES
+    fn parse(rule: &str) -> Option<Self> {
+        let rule = rule.trim();
+        let (host, rule) = match rule.split_once(':') {
+            Some((host, rule)) => (Some(host.trim().to_lowercase()), rule.trim()),
+            None => (None, rule),
+        };
+        let (name, value) = match rule.split_once('=') {
+            Some((name, value)) => (name.trim(), Some(value.trim().to_string())),
+            None => (rule, None),
+        };
+        let (name, prefix) = match name.strip_suffix('*') {
+            Some(name) => (name, true),
+            None => (name, false),
+        };
+        if name.is_empty() && !prefix {
+            return None;
+        }
+
+        Some(Self {
+            host: host.filter(|host| !host.is_empty()),
+            name: name.to_lowercase(),
+            prefix,
+            value,
+        })
+    }
+
+    /// 判断参数是否匹配该规则
+    fn matches(&self, host: &str, name: &str, value: &str) -> bool {
+        if let Some(rule_host) = &self.host {
+            let is_subdomain = host
+                .strip_suffix(rule_host.as_str())
+                .is_some_and(|rest| rest.ends_with('.'));
+            if host != rule_host && !is_subdomain {
+                return false;
+            }
+        }
+
+        let name = name.to_lowercase();
+        let name_matches = if self.prefix {
+            name.starts_with(&self.name)
+        } else {
+            name == self.name
+        };
+        name_matches
+            && self
+                .value
+                .as_ref()
+                .is_none_or(|rule_value| rule_value.eq_ignore_ascii_case(value))
+    }
+}
+
+/// 链接规范化规则
+///
+/// 用于去除文章链接中的跟踪参数，使同一篇文章的不同链接可以正确去重，
+/// 并避免打开链接时泄露来源信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkRules {
+    /// 需要去除的参数
+    strip: Vec<ParamRule>,
+    /// 即使匹配strip规则也保留的参数
+    keep: Vec<ParamRule>,
+}
+
+impl Default for LinkRules {
+    fn default() -> Self {
+        Self::new(true, &[], &[])
+    }
+}
+
+impl LinkRules {
+    /// 创建规则，use_defaults为false时只使用用户配置的规则
+    pub fn new(use_defaults: bool, strip: &[String], keep: &[String]) -> Self {
+        let defaults = DEFAULT_STRIP_RULES.iter().filter(|_| use_defaults).copied();
+        Self {
+            strip: defaults
+                .chain(strip.iter().map(String::as_str))
+                .filter_map(ParamRule::parse)
+                .collect(),
+            keep: keep
+                .iter()
+                .filter_map(|rule| ParamRule::parse(rule))
+                .collect(),
+        }
+    }
+
+    /// 规则的指纹，规则或规范化方式变化时改变
+    pub fn fingerprint(&self) -> String {
+        content_hash(&CANONICALIZE_VERSION.to_string(), &format!("{:?}", self))
+    }
+
+    /// 规范化链接
+    ///
+    /// 依次执行：相对地址按base_url解析为绝对地址、域名转为小写、去除默认端口、
+    /// 去除#fragment、去除跟踪参数。无法解析的链接只去除#fragment
+    pub fn canonicalize(&self, link: &str, base_url: Option<&str>) -> String {
+        let link = link.trim();
+        if link.is_empty() {
+            return String::new();
+        }
+
+        // 解析时会将http(s)地址的域名转为小写，并去除默认端口
+        let parsed = Url::parse(link).or_else(|e| {
+            base_url
+                .and_then(|base_url| Url::parse(base_url).ok())
+                .and_then(|base_url| base_url.join(link).ok())
+                .ok_or(e)
+        });
+        let Ok(mut url) = parsed else {
+            return strip_fragment(link).to_string();
+        };
+
+        url.set_fragment(None);
+        if matches!(url.scheme(), "http" | "https") {
+            self.strip_params(&mut url);
+        }
+        url.to_string()
+    }
+
+    /// 去除跟踪参数，保留其余参数的原始顺序和编码
+    fn strip_params(&self, url: &mut Url) {
+        let Some(query) = url.query().map(str::to_string) else {
+            return;
+        };
+        let host = url.host_str().unwrap_or("").to_string();
+
+        let kept: Vec<&str> = query
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .filter(|pair| {
+                let (name, value) = url::form_urlencoded::parse(pair.as_bytes())
+                    .next()
+                    .unwrap_or_default();
+                let is_tracking = self
+                    .strip
+                    .iter()
+                    .any(|rule| rule.matches(&host, &name, &value));
+                !is_tracking
+                    || self
+                        .keep
+                        .iter()
+                        .any(|rule| rule.matches(&host, &name, &value))
+            })
+            .collect();
+
+        let query = kept.join("&");
+        url.set_query((!query.is_empty()).then_some(query.as_str()));
+    }
+}
+
+/// 去除#fragment部分
+fn strip_fragment(link: &str) -> &str {
+    link.split('#').next().unwrap_or(link)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn canonicalize(link: &str) -> String {
+        LinkRules::default().canonicalize(link, None)
+    }
+
+    fn rules(strip: &[&str], keep: &[&str]) -> LinkRules {
+        let to_strings = |rules: &[&str]| {
+            rules
+                .iter()
+                .map(|rule| rule.to_string())
+                .collect::<Vec<_>>()
+        };
+        LinkRules::new(false, &to_strings(strip), &to_strings(keep))
+    }
+
+    #[test]
+    fn strips_utm_and_click_ids() {
+        assert_eq!(
+            canonicalize(
+                "https://example.com/post?utm_source=rss&utm_medium=feed&id=42&UTM_Campaign=x"
+            ),
+            "https://example.com/post?id=42"
+        );
+        assert_eq!(
+            canonicalize("https://example.com/post?fbclid=abc&gclid=def"),
+            "https://example.com/post"
+        );
+    }
+
+    #[test]
+    fn keeps_other_params_in_order_and_encoding() {
+        assert_eq!(
+            canonicalize("https://example.com/search?q=a%20b&utm_source=x&page=2&tag=%E4%B8%AD"),
+            "https://example.com/search?q=a%20b&page=2&tag=%E4%B8%AD"
+        );
+    }
+
+    #[test]
+    fn normalizes_host_port_and_fragment() {
+        assert_eq!(
+            canonicalize("HTTPS://Example.COM:443/Post#comments"),
+            "https://example.com/Post"
+        );
+        assert_eq!(
+            canonicalize("http://example.com:8080/post"),
+            "http://example.com:8080/post"
+        );
+    }
+
+    #[test]
+    fn resolves_relative_links() {
+        let rules = LinkRules::default();
+        assert_eq!(
+            rules.canonicalize(
+                "../post?utm_source=rss",
+                Some("https://example.com/blog/feed/")
+            ),
+            "https://example.com/blog/post"
+        );
+        assert_eq!(rules.canonicalize("/post#top", None), "/post");
+    }
+
+    #[test]
+    fn matches_values() {
+        assert_eq!(
+            canonicalize("https://example.com/post?from=rss&id=1"),
+            "https://example.com/post?id=1"
+        );
+        assert_eq!(
+            canonicalize("https://example.com/post?from=home&id=1"),
+            "https://example.com/post?from=home&id=1"
+        );
+    }
+
+    #[test]
+    fn applies_host_rules_to_subdomains_only() {
+        assert_eq!(
+            canonicalize("https://www.youtube.com/watch?v=abc&feature=share"),
+            "https://www.youtube.com/watch?v=abc"
+        );
+        assert_eq!(
+            canonicalize("https://example.com/post?feature=share"),
+            "https://example.com/post?feature=share"
+        );
+        // 只是以规则域名结尾的其他域名不匹配
+        assert_eq!(
+            canonicalize("https://notyoutube.com/watch?feature=share"),
+            "https://notyoutube.com/watch?feature=share"
+        );
+    }
+
+    #[test]
+    fn applies_prefix_and_keep_rules() {
+        let rules = rules(&["ref_*", "example.com:session"], &["ref_id"]);
+        assert_eq!(
+            rules.canonicalize(
+                "https://example.com/a?ref_src=x&ref_id=1&session=2&utm_source=y",
+                None
+            ),
+            "https://example.com/a?ref_id=1&utm_source=y"
+        );
+        assert_eq!(
+            rules.canonicalize("https://other.com/a?session=2", None),
+            "https://other.com/a?session=2"
+        );
+    }
+
+    #[test]
+    fn leaves_non_http_links_alone() {
+        assert_eq!(
+            canonicalize("mailto:someone@example.com?utm_source=rss"),
+            "mailto:someone@example.com?utm_source=rss"
+        );
+        assert_eq!(
+            canonicalize("magnet:?xt=urn:btih:abc&utm_source=rss"),
+            "magnet:?xt=urn:btih:abc&utm_source=rss"
+        );
+        assert_eq!(canonicalize("  "), "");
+    }
+
+    #[test]
+    fn parses_rules() {
+        assert_eq!(ParamRule::parse(""), None);
+        assert_eq!(
+            ParamRule::parse(" Example.com : Ref=RSS "),
+            Some(ParamRule {
+                host: Some("example.com".to_string()),
+                name: "ref".to_string(),
+                prefix: false,
+                value: Some("RSS".to_string()),
+            })
+        );
+    }
+}
@@ -0,0 +1,508 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+
+/// 常见的时区缩写及其UTC偏移（分钟）
+///
+/// 中文源中的CST几乎都指中国标准时间，因此按+08:00处理，而不是RFC 822中的美国中部时间
+const TIMEZONE_ABBREVIATIONS: &[(&str, i32)] = &[
+    ("UT", 0),
+    ("UTC", 0),
+    ("GMT", 0),
+    ("Z", 0),
+    ("WET", 0),
+    ("EST", -5 * 60),
+    ("EDT", -4 * 60),
+    ("CST", 8 * 60),
+    ("CDT", -5 * 60),
+    ("MST", -7 * 60),
+    ("MDT", -6 * 60),
+    ("PST", -8 * 60),
+    ("PDT", -7 * 60),
+    ("AKST", -9 * 60),
+    ("AKDT", -8 * 60),
+    ("HST", -10 * 60),
+    ("BST", 60),
+    ("WEST", 60),
+    ("CET", 60),
+    ("CEST", 2 * 60),
+    ("EET", 2 * 60),
+    ("EEST", 3 * 60),
+    ("MSK", 3 * 60),
+    ("IST", 5 * 60 + 30),
+    ("HKT", 8 * 60),
+    ("SGT", 8 * 60),
+    ("AWST", 8 * 60),
+    ("JST", 9 * 60),
+    ("KST", 9 * 60),
+    ("ACST", 9 * 60 + 30),
+    ("AEST", 10 * 60),
+    ("AEDT", 11 * 60),
+    ("NZST", 12 * 60),
+    ("NZDT", 13 * 60),
+];
+
+/// 英文月份名称，同时匹配缩写
+const MONTHS: &[&str] = &[
+    "january",
+    "february",
+    "march",
+    "april",
+    "may",
+    "june",
+    "july",
+    "august",
+    "september",
+    "october",
+    "november",
+    "december",
+];
+
+/// 英文星期名称，同时匹配缩写
+const WEEKDAYS: &[&str] = &[
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// 宽松地解析RSS源中的日期
+///
+/// 支持RFC 2822及其常见变体（缺少星期、完整月份名、时区缩写、GMT+8等）、
+/// RFC 3339/ISO 8601（包括空格分隔和缺少时区的格式）、中文日期格式和Unix时间戳。
+/// 没有时区信息的日期按本地时区处理，无法解析时返回None
+pub fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    let value = strip_comments(value);
+    let value = value.trim();
+    if value.is_empty() {
+        return None;
+    }
+
+    // chrono按RFC 822将CST解析为美国中部时间，这种日期交给宽松解析按时区缩写表处理
+    let has_cst = value
+        .split_whitespace()
+        .last()
+        .is_some_and(|zone| zone.eq_ignore_ascii_case("CST"));
+    let rfc2822 = if has_cst {
+        None
+    } else {
+        DateTime::parse_from_rfc2822(value).ok()
+    };
+
+    rfc2822
+        .or_else(|| DateTime::parse_from_rfc3339(value).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+        .or_else(|| parse_timestamp(value))
+        .or_else(|| parse_iso_like(value))
+        .or_else(|| parse_rfc2822_like(value))
+        .or_else(|| parse_chinese(value))
+}
+
+/// 去掉括号中的注释，例如"+0800 (CST)"中的"(CST)"
+fn strip_comments(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+    let mut depth = 0usize;
+    for c in value.chars() {
+        match c {
+            '(' | '（' => depth += 1,
+            ')' | '）' if depth > 0 => depth -= 1,
+            _ if depth == 0 => result.push(c),
+            _ => {}
+        }
+    }
+    result
+}
+
+/// 解析Unix时间戳（秒或毫秒）
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    if !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let timestamp: i64 = value.parse().ok()?;
+    match value.len() {
+        10 => Utc.timestamp_opt(timestamp, 0).single(),
+        13 => Utc.timestamp_millis_opt(timestamp).single(),
+        _ => None,
+    }
+}
+
+/// 解析时区：+0800、+08:00、+08、GMT+8、UTC+08:00或时区缩写
+fn parse_offset(value: &str) -> Option<FixedOffset> {
+    let value = value.trim();
+    let upper = value.to_uppercase();
+    if let Some((_, minutes)) = TIMEZONE_ABBREVIATIONS
+        .iter()
+        .find(|(name, _)| *name == upper)
+    {
+        return FixedOffset::east_opt(minutes * 60);
+    }
+
+    let numeric = upper
+        .strip_prefix("GMT")
+        .or_else(|| upper.strip_prefix("UTC"))
+        .unwrap_or(&upper);
+    let sign = match numeric.chars().next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let digits = numeric[1..].replace(':', "");
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().ok()?, 0),
+        3 | 4 => {
+            let split = digits.len() - 2;
+            (
+                digits[..split].parse::<i32>().ok()?,
+                digits[split..].parse::<i32>().ok()?,
+            )
+        }
+        _ => return None,
+    };
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// 解析按空格分隔的时区部分
+///
+/// 同时有数字偏移和时区缩写时（"+0800 CST"）以数字偏移为准，否则合并各部分解析（"GMT +8"）
+fn parse_zone(tokens: &[&str]) -> Option<FixedOffset> {
+    tokens
+        .iter()
+        .filter(|token| token.starts_with(['+', '-']))
+        .find_map(|token| parse_offset(token))
+        .or_else(|| parse_offset(&tokens.concat()))
+}
+
+/// 按时区转换为UTC时间，没有时区时按本地时区处理
+fn to_utc(datetime: NaiveDateTime, offset: Option<FixedOffset>) -> Option<DateTime<Utc>> {
+    match offset {
+        Some(offset) => offset
+            .from_local_datetime(&datetime)
+            .single()
+            .map(|dt| dt.with_timezone(&Utc)),
+        None => Local
+            .from_local_datetime(&datetime)
+            .earliest()
+            .map(|dt| dt.with_timezone(&Utc)),
+    }
+}
+
+/// 解析时间部分，支持省略秒和带小数的秒
+fn parse_time(value: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(value, "%H:%M:%S%.f")
+        .or_else(|_| NaiveTime::parse_from_str(value, "%H:%M"))
+        .ok()
+}
+
+/// 解析类似ISO 8601的日期，例如"2024-05-01 10:00:00 +0800"、"2024/05/01T10:00"和"2024-05-01"
+fn parse_iso_like(value: &str) -> Option<DateTime<Utc>> {
+    let value = value.replace('/', "-");
+    let (date, rest) = match value.find(['T', 't', ' ']) {
+        Some(pos) => (&value[..pos], value[pos + 1..].trim()),
+        None => (value.as_str(), ""),
+    };
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()?;
+    if rest.is_empty() {
+        return to_utc(date.and_time(NaiveTime::MIN), None);
+    }
+
+    // 时间之后紧跟或以空格分隔的时区
+    let time_end = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == ':' || c == '.'))
+        .unwrap_or(rest.len());
+    let time = parse_time(&rest[..time_end])?;
+    let zone: Vec<&str> = rest[time_end..].split_whitespace().collect();
+    let offset = if zone.is_empty() {
+        None
+    } else {
+        Some(parse_zone(&zone)?)
+    };
+    to_utc(date.and_time(time), offset)
+}
+
+/// 匹配英文月份名称或缩写，返回月份（1-12）
+fn parse_month(value: &str) -> Option<u32> {
+    let value = value.trim_end_matches('.').to_lowercase();
+    if value.len() < 3 {
+        return None;
+    }
+    MONTHS
+        .iter()
+        .position(|month| month.starts_with(&value))
+        .map(|index| index as u32 + 1)
+}
+
+/// 解析RFC 2822的常见变体，例如"Wednesday, 01 May 2024 10:00 GMT+8"、
+/// "May 1, 2024 10:00:00 PST"和"Wed May 01 10:00:00 2024"
+fn parse_rfc2822_like(value: &str) -> Option<DateTime<Utc>> {
+    let mut tokens: Vec<&str> = value
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .collect();
+
+    // 去掉星期
+    if let Some(first) = tokens.first() {
+        let first = first.trim_end_matches('.').to_lowercase();
+        if first.len() >= 3 && WEEKDAYS.iter().any(|day| day.starts_with(&first)) {
+            tokens.remove(0);
+        }
+    }
+    if tokens.len() < 3 {
+        return None;
+    }
+
+    // 支持"01 May 2024 10:00"、"May 01 2024 10:00"和"May 01 10:00:00 2024"三种顺序
+    let (day, month, year, time, zone) = if let Some(month) = parse_month(tokens[0]) {
+        if tokens.len() >= 4 && tokens[2].contains(':') {
+            (tokens[1], month, tokens[3], Some(tokens[2]), &tokens[4..])
+        } else {
+            (
+                tokens[1],
+                month,
+                tokens[2],
+                tokens.get(3).copied(),
+                tokens.get(4..).unwrap_or(&[]),
+            )
+        }
+    } else {
+        (
+            tokens[0],
+            parse_month(tokens[1])?,
+            tokens[2],
+            tokens.get(3).copied(),
+            tokens.get(4..).unwrap_or(&[]),
+        )
+    };
+
+    let day: u32 = day
+        .trim_end_matches(['s', 't', 'n', 'd', 'r', 'h'])
+        .parse()
+        .ok()?;
+    let year: i32 = match year.parse::<i32>().ok()? {
+        // 两位年份按RFC 2822的规则处理
+        year @ 0..=49 => year + 2000,
+        year @ 50..=99 => year + 1900,
+        year => year,
+    };
+    let date = NaiveDate::from_ymd_opt(year, month, day)?;
+    let time = match time {
+        Some(time) => parse_time(time)?,
+        None => NaiveTime::MIN,
+    };
+
+    // RFC 2822中没有时区的日期视为UTC
+    let offset = if zone.is_empty() {
+        FixedOffset::east_opt(0)
+    } else {
+        parse_zone(zone)
+    };
+    to_utc(date.and_time(time), Some(offset?))
+}
+
+/// 解析中文日期，例如"2024年5月1日 10:00"、"2024年05月01日 下午3点20分"和"2024年5月1日 星期三"
+fn parse_chinese(value: &str) -> Option<DateTime<Utc>> {
+    if !value.contains('年') {
+        return None;
+    }
+
+    let mut offset = None;
+    let mut value = value.to_string();
+    if value.contains("北京时间") {
+        offset = FixedOffset::east_opt(8 * 3600);
+        value = value.replace("北京时间", " ");
+    }
+    let afternoon = ["下午", "晚上"].iter().any(|word| value.contains(word));
+    for word in ["上午", "下午", "中午", "晚上", "凌晨", "早上"] {
+        value = value.replace(word, " ");
+    }
+    // 去掉星期
+    for prefix in ["星期", "周", "礼拜"] {
+        while let Some(pos) = value.find(prefix) {
+            let end = pos
+                + prefix.len()
+                + value[pos + prefix.len()..]
+                    .chars()
+                    .next()
+                    .map(char::len_utf8)
+                    .unwrap_or(0);
+            value.replace_range(pos..end, " ");
+        }
+    }
+
+    let normalized = value
+        .replace(['年', '月'], "-")
+        .replace(['日', '号', '秒'], " ")
+        .replace(['时', '点', '分'], ":");
+    // "10时"、"10时20分"转换后末尾多出的冒号补全为完整时间
+    let mut normalized = normalized
+        .split_whitespace()
+        .map(|part| match part.matches(':').count() {
+            1 if part.ends_with(':') => format!("{}00", part),
+            2 if part.ends_with(':') => part.trim_end_matches(':').to_string(),
+            _ => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    if let Some(offset) = offset {
+        normalized.push_str(&format!(" {}", offset));
+    }
+
+    let parsed = parse_iso_like(&normalized)?;
+    // 12小时制的下午时间，小时小于12时加12小时
+    let hour = normalized
+        .split_whitespace()
+        .nth(1)
+        .and_then(|time| time.split(':').next())
+        .and_then(|hour| hour.parse::<u32>().ok());
+    if afternoon && hour.is_some_and(|hour| hour < 12) {
+        return Some(parsed + chrono::Duration::hours(12));
+    }
+    Some(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn local(year: i32, month: u32, day: u32, hour: u32, minute: u32) -> DateTime<Utc> {
+        let datetime = NaiveDate::from_ymd_opt(year, month, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap();
+        to_utc(datetime, None).unwrap()
+    }
+
+    fn assert_parsed(value: &str, expected: DateTime<Utc>) {
+        assert_eq!(parse_date(value), Some(expected), "解析 {:?}", value);
+    }
+
+    #[test]
+    fn parses_rfc2822() {
+        let expected = utc("2024-05-01T10:00:00Z");
+        assert_parsed("Wed, 01 May 2024 10:00:00 GMT", expected);
+        assert_parsed("Wed, 01 May 2024 10:00:00 +0000", expected);
+        assert_parsed("Wed, 01 May 2024 18:00:00 +0800", expected);
+        // 星期与日期不符时忽略星期
+        assert_parsed("Mon, 01 May 2024 10:00:00 GMT", expected);
+    }
+
+    #[test]
+    fn parses_rfc2822_variants() {
+        let expected = utc("2024-05-01T10:00:00Z");
+        // 省略星期和秒
+        assert_parsed("01 May 2024 10:00 GMT", expected);
+        // 完整的星期和月份名称
+        assert_parsed("Wednesday, 01 May 2024 10:00:00 GMT", expected);
+        assert_parsed(
+            "01 September 2024 10:00:00 GMT",
+            utc("2024-09-01T10:00:00Z"),
+        );
+        // 两位年份
+        assert_parsed("Wed, 01 May 24 10:00:00 GMT", expected);
+        // 没有时区时视为UTC
+        assert_parsed("Wed, 01 May 2024 10:00:00", expected);
+        // 月份在日期前
+        assert_parsed("May 1, 2024 02:00:00 PST", expected);
+        // asctime格式
+        assert_parsed("Wed May 01 10:00:00 2024", expected);
+    }
+
+    #[test]
+    fn parses_zone_abbreviations_and_gmt_offsets() {
+        assert_parsed("01 May 2024 06:00:00 EDT", utc("2024-05-01T10:00:00Z"));
+        assert_parsed("01 May 2024 10:00:00 UT", utc("2024-05-01T10:00:00Z"));
+        assert_parsed("01 May 2024 18:00:00 GMT+8", utc("2024-05-01T10:00:00Z"));
+        assert_parsed("01 May 2024 18:00:00 GMT +8", utc("2024-05-01T10:00:00Z"));
+        assert_parsed(
+            "01 May 2024 18:30:00 UTC+08:30",
+            utc("2024-05-01T10:00:00Z"),
+        );
+        assert_eq!(parse_date("01 May 2024 10:00:00 XYZ"), None);
+    }
+
+    #[test]
+    fn treats_cst_as_china_time() {
+        let expected = utc("2024-05-01T10:00:00Z");
+        assert_parsed("Wed, 01 May 2024 18:00:00 CST", expected);
+        assert_parsed("2024-05-01 18:00:00 CST", expected);
+    }
+
+    #[test]
+    fn prefers_numeric_offset_and_drops_zone_comments() {
+        let expected = utc("2024-05-01T10:00:00Z");
+        assert_parsed("Wed, 01 May 2024 04:00:00 -0600 (CST)", expected);
+        assert_parsed("Wed, 01 May 2024 04:00:00 -0600 CST", expected);
+        assert_parsed(
+            "Wed, 01 May 2024 18:00:00 +0800 (China Standard Time)",
+            expected,
+        );
+        assert_parsed("2024-05-01 04:00:00 -0600 CST", expected);
+        assert_parsed("2024-05-01 18:00:00 +0800（中国标准时间）", expected);
+    }
+
+    #[test]
+    fn parses_rfc3339_and_iso_like() {
+        let expected = utc("2024-05-01T10:00:00Z");
+        assert_parsed("2024-05-01T10:00:00Z", expected);
+        assert_parsed("2024-05-01T18:00:00+08:00", expected);
+        assert_parsed("2024-05-01T10:00:00.123Z", utc("2024-05-01T10:00:00.123Z"));
+        assert_parsed("2024-05-01 18:00:00+0800", expected);
+        assert_parsed("2024-05-01 18:00 +08:00", expected);
+        assert_parsed("2024/05/01 10:00:00 UTC", expected);
+        // 没有时区时按本地时区处理
+        assert_parsed("2024-05-01 10:00:00", local(2024, 5, 1, 10, 0));
+        assert_parsed("2024/5/1 10:00", local(2024, 5, 1, 10, 0));
+    }
+
+    #[test]
+    fn parses_date_only() {
+        assert_parsed("2024-05-01", local(2024, 5, 1, 0, 0));
+        assert_parsed("2024/05/01", local(2024, 5, 1, 0, 0));
+    }
+
+    #[test]
+    fn parses_timestamps() {
+        let expected = utc("2024-05-01T10:00:00Z");
+        assert_parsed("1714557600", expected);
+        assert_parsed("1714557600000", expected);
+        assert_eq!(parse_date("12345"), None);
+    }
+
+    #[test]
+    fn parses_chinese_dates() {
+        assert_parsed("2024年5月1日", local(2024, 5, 1, 0, 0));
+        assert_parsed("2024年05月01日 10:00", local(2024, 5, 1, 10, 0));
+        assert_parsed("2024年5月1日 星期三 10:00", local(2024, 5, 1, 10, 0));
+        assert_parsed("2024年5月1日 下午3点20分", local(2024, 5, 1, 15, 20));
+        assert_parsed("2024年5月1日 上午9时", local(2024, 5, 1, 9, 0));
+        assert_parsed("2024年5月1日 18:00 北京时间", utc("2024-05-01T10:00:00Z"));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        for value in [
+            "",
+            "   ",
+            "garbage",
+            "2024-13-01",
+            "2024-02-30 10:00",
+            "32 May 2024 10:00 GMT",
+            "Wed, 01 Foo 2024 10:00:00 GMT",
+            "2024年13月1日",
+        ] {
+            assert_eq!(parse_date(value), None, "解析 {:?}", value);
+        }
+    }
+}
@@ -1,9 +1,23 @@
-use crate::models::{AIPlatform, Article, Feed, FeedGroup};
+use crate::canonical::LinkRules;
+use crate::error::AppError;
+use crate::models::{
+    AIPlatform, Article, ArticleRevision, Enclosure, Feed, FeedGroup, FeedHealth, FeedHealthStatus,
+    FeedRequestOptions, WebSubSubscription, content_hash,
+};
+use crate::sanitizer::sanitize_html;
 use chrono::{DateTime, TimeZone, Utc};
 use opml::{OPML, Outline};
-use rusqlite::{Connection, Result, params};
+use rusqlite::{Connection, OptionalExtension, Result, params};
+use std::collections::HashSet;
 use std::path::PathBuf;
 
+/// websub_subscriptions表的查询列，与websub_subscription_from_row的顺序一致
+const WEBSUB_COLUMNS: &str =
+    "feed_id, hub, topic, token, secret, state, lease_expires, last_requested, last_error";
+
+/// settings表中保存上次重写文章链接时链接规则指纹的键
+const LINK_RULES_SETTING: &str = "link_rules_fingerprint";
+
 /// 数据库管理器
 pub struct DbManager {
     conn: Connection,
@@ -61,6 +75,210 @@ impl DbManager {
         Ok(count > 0)
     }
 
+    /// 将自定义请求设置序列化为JSON，没有设置时保存为NULL
+    ///
+    /// FeedRequestOptions序列化时会隐藏密钥，保存到数据库时需要完整的值
+    fn request_options_to_json(options: &FeedRequestOptions) -> Option<String> {
+        if options.is_empty() {
+            None
+        } else {
+            serde_json::to_string(&serde_json::json!({
+                "headers": options.headers,
+                "cookie": options.cookie,
+                "username": options.username,
+                "password": options.password,
+                "bearer_token": options.bearer_token,
+                "user_agent": options.user_agent,
+                "bypass_proxy": options.bypass_proxy,
+            }))
+            .ok()
+        }
+    }
+
+    /// 从JSON解析自定义请求设置
+    fn request_options_from_json(json: Option<String>) -> FeedRequestOptions {
+        json.and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// 导出到OPML的源地址，去掉URL中内嵌的用户名和密码
+    fn opml_feed_url(feed_url: &str) -> String {
+        match url::Url::parse(feed_url) {
+            Ok(mut parsed) if !parsed.username().is_empty() || parsed.password().is_some() => {
+                let _ = parsed.set_username("");
+                let _ = parsed.set_password(None);
+                parsed.to_string()
+            }
+            _ => feed_url.to_string(),
+        }
+    }
+
+    /// 为没有链接的旧文章补充内容哈希作为guid
+    ///
+    /// 旧版本按链接去重，没有链接的文章会被合并成一条，这里先给它们分配各自的标识。
+    /// 旧文章保存的是清理HTML之前的内容，与新获取的文章一样通过linkless_guid计算，避免升级后重复。
+    /// 有链接的旧文章guid保持为空，下次更新时匹配到源中的同一篇文章后再补充真实的guid
+    fn migrate_article_guids(conn: &Connection) -> Result<()> {
+        let rows = {
+            let mut stmt = conn.prepare(
+                "SELECT id, title, content FROM articles WHERE link = '' AND guid IS NULL",
+            )?;
+            stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?
+        };
+        for (id, title, content) in rows {
+            conn.execute(
+                "UPDATE articles SET guid = ? WHERE id = ?",
+                params![Self::linkless_guid(&title, &content), id],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 根据标题和内容计算稳定的哈希，用于既没有guid也没有链接的文章
+    fn content_hash(title: &str, content: &str) -> String {
+        content_hash(title, content)
+    }
+
+    /// 没有guid和链接的文章的标识，内容先经过与解析时相同的HTML清理，
+    /// 使清理前保存的旧文章与新获取的文章得到相同的结果
+    fn linkless_guid(title: &str, content: &str) -> String {
+        Self::content_hash(title, &sanitize_html(content))
+    }
+
+    /// 用于检测文章修改的哈希
+    ///
+    /// 优先使用解析时计算的源中原始内容的哈希，避免清理规则、链接规则或黑名单变化被误判为修改；
+    /// 没有时（非源中解析的文章）使用原始摘要或内容计算
+    fn revision_hash(article: &Article) -> String {
+        article.source_hash.clone().unwrap_or_else(|| {
+            let content = article.summary.as_deref().unwrap_or(&article.content);
+            Self::content_hash(&article.title, content)
+        })
+    }
+
+    /// 文章的唯一标识：优先使用源提供的guid，没有guid和链接时使用内容哈希
+    fn article_guid(article: &Article) -> Option<String> {
+        article
+            .guid
+            .as_deref()
+            .map(str::trim)
+            .filter(|guid| !guid.is_empty())
+            .map(|guid| guid.to_string())
+            .or_else(|| {
+                article
+                    .link
+                    .is_empty()
+                    .then(|| Self::linkless_guid(&article.title, &article.content))
+            })
+    }
+
+    /// 查找已保存的同一篇文章，返回文章ID
+    ///
+    /// 有guid时按guid匹配，找不到时再匹配尚未记录guid的旧文章的链接（guid在add_article中补充）；
+    /// 没有guid时按链接匹配
+    fn find_existing_article(&self, article: &Article) -> Result<Option<i64>> {
+        let guid = Self::article_guid(article);
+        if let Some(guid) = &guid {
+            let id = self
+                .conn
+                .query_row(
+                    "SELECT id FROM articles WHERE feed_id = ? AND guid = ?",
+                    params![article.feed_id, guid],
+                    |row| row.get(0),
+                )
+                .optional()?;
+            if id.is_some() || article.link.is_empty() {
+                return Ok(id);
+            }
+        }
+
+        let sql = if guid.is_some() {
+            "SELECT id FROM articles WHERE feed_id = ? AND link = ? AND guid IS NULL"
+        } else {
+            "SELECT id FROM articles WHERE feed_id = ? AND link = ?"
+        };
+        self.conn
+            .query_row(
+                sql,
+                params![article.feed_id, article.link.as_str()],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    /// 判断源中的文章与已保存的版本相比是否被发布者修改，只读取不修改数据库
+    ///
+    /// 源提供了修改时间时，只有修改时间更新才视为修改；没有修改时间时（RSS），
+    /// 需要连续两次更新获取到相同的新内容才视为修改，避免动态生成的内容每次都被当作新版本
+    fn is_article_changed(&self, id: i64, article: &Article) -> Result<bool> {
+        let (stored_hash, pending_hash, stored_updated): (
+            Option<String>,
+            Option<String>,
+            Option<i64>,
+        ) = self.conn.query_row(
+            "SELECT content_hash, pending_hash, updated_at FROM articles WHERE id = ?",
+            params![id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+
+        // 旧版本保存的文章没有哈希，不视为修改（保存时补充哈希）
+        let Some(stored_hash) = stored_hash else {
+            return Ok(false);
+        };
+        let hash = Self::revision_hash(article);
+        if stored_hash == hash {
+            return Ok(false);
+        }
+
+        Ok(match (article.updated, stored_updated) {
+            (Some(updated), Some(stored_updated)) => updated.timestamp() > stored_updated,
+            _ => pending_hash.as_deref() == Some(hash.as_str()),
+        })
+    }
+
+    /// 将文章的当前版本保存到历史版本表，并用源中修改后的内容更新文章
+    ///
+    /// 已有的翻译随旧版本失效，使用新文章中的翻译（未翻译时清空）
+    fn revise_article(&self, id: i64, article: &Article, categories_str: &str) -> Result<()> {
+        let tx = self.conn.unchecked_transaction()?;
+        let now = Utc::now();
+
+        tx.execute(
+            r#"INSERT INTO article_revisions (article_id, title, content, updated_at, replaced_at)
+               SELECT id, title, content, updated_at, ? FROM articles WHERE id = ?"#,
+            params![now.timestamp(), id],
+        )?;
+        tx.execute(
+            r#"UPDATE articles
+               SET title = ?, content = ?, summary = ?, thumbnail = COALESCE(?, thumbnail),
+                   author = ?, categories = ?, translated_title = ?, translated_content = ?,
+                   content_hash = ?, pending_hash = NULL, updated_at = ?
+               WHERE id = ?"#,
+            params![
+                article.title.as_str(),
+                article.content.as_str(),
+                article.summary.as_deref(),
+                article.thumbnail.as_deref(),
+                article.author.as_deref(),
+                categories_str,
+                article.translated_title.as_deref(),
+                article.translated_content.as_deref(),
+                Self::revision_hash(article),
+                article.updated.unwrap_or(now).timestamp(),
+                id
+            ],
+        )?;
+
+        tx.commit()
+    }
+
     /// 创建数据库表
     fn create_tables(conn: &Connection) -> Result<()> {
         // 创建分组表
@@ -123,6 +341,57 @@ impl DbManager {
             )?;
         }
 
+        // 为现有数据库添加etag列（如果不存在）
+        if !Self::column_exists(conn, "feeds", "etag")? {
+            conn.execute("ALTER TABLE feeds ADD COLUMN etag TEXT", [])?;
+        }
+
+        // 为现有数据库添加last_modified列（如果不存在）
+        if !Self::column_exists(conn, "feeds", "last_modified")? {
+            conn.execute("ALTER TABLE feeds ADD COLUMN last_modified TEXT", [])?;
+        }
+
+        // 为现有数据库添加update_interval列（如果不存在）
+        if !Self::column_exists(conn, "feeds", "update_interval")? {
+            conn.execute("ALTER TABLE feeds ADD COLUMN update_interval INTEGER", [])?;
+        }
+
+        // 为现有数据库添加last_error_kind列（如果不存在）
+        if !Self::column_exists(conn, "feeds", "last_error_kind")? {
+            conn.execute("ALTER TABLE feeds ADD COLUMN last_error_kind TEXT", [])?;
+        }
+
+        // 为现有数据库添加last_http_status列（如果不存在）
+        if !Self::column_exists(conn, "feeds", "last_http_status")? {
+            conn.execute("ALTER TABLE feeds ADD COLUMN last_http_status INTEGER", [])?;
+        }
+
+        // 为现有数据库添加failing_since列（如果不存在）
+        if !Self::column_exists(conn, "feeds", "failing_since")? {
+            conn.execute("ALTER TABLE feeds ADD COLUMN failing_since INTEGER", [])?;
+        }
+
+        // 为现有数据库添加always_use_browser列（如果不存在）
+        if !Self::column_exists(conn, "feeds", "always_use_browser")? {
+            conn.execute(
+                "ALTER TABLE feeds ADD COLUMN always_use_browser BOOLEAN NOT NULL DEFAULT FALSE",
+                [],
+            )?;
+        }
+
+        // 为现有数据库添加request_options列（如果不存在），以JSON保存自定义请求头和认证信息
+        if !Self::column_exists(conn, "feeds", "request_options")? {
+            conn.execute("ALTER TABLE feeds ADD COLUMN request_options TEXT", [])?;
+        }
+
+        // 为现有数据库添加fetch_full_content列（如果不存在）
+        if !Self::column_exists(conn, "feeds", "fetch_full_content")? {
+            conn.execute(
+                "ALTER TABLE feeds ADD COLUMN fetch_full_content BOOLEAN NOT NULL DEFAULT FALSE",
+                [],
+            )?;
+        }
+
         // 创建文章表
         conn.execute(
             r#"
@@ -157,7 +426,43 @@ impl DbManager {
             )?;
         }
 
+        // 为现有数据库添加guid列（如果不存在），保存RSS的<guid>或Atom的<id>，作为文章的唯一标识
+        if !Self::column_exists(conn, "articles", "guid")? {
+            conn.execute("ALTER TABLE articles ADD COLUMN guid TEXT", [])?;
+            Self::migrate_article_guids(conn)?;
+        }
+
+        // 为现有数据库添加updated_at列（如果不存在），保存文章的最后修改时间
+        if !Self::column_exists(conn, "articles", "updated_at")? {
+            conn.execute("ALTER TABLE articles ADD COLUMN updated_at INTEGER", [])?;
+        }
+
+        // 为现有数据库添加content_hash列（如果不存在），用于检测发布者是否修改了文章
+        if !Self::column_exists(conn, "articles", "content_hash")? {
+            conn.execute("ALTER TABLE articles ADD COLUMN content_hash TEXT", [])?;
+        }
+
+        // 为现有数据库添加pending_hash列（如果不存在），保存与已保存版本不同、等待下次更新确认的哈希
+        if !Self::column_exists(conn, "articles", "pending_hash")? {
+            conn.execute("ALTER TABLE articles ADD COLUMN pending_hash TEXT", [])?;
+        }
+
+        // 为现有数据库添加first_seen列（如果不存在），保存首次获取到文章的时间
+        // 旧文章无法得知首次获取的时间，使用发布时间代替
+        if !Self::column_exists(conn, "articles", "first_seen")? {
+            conn.execute("ALTER TABLE articles ADD COLUMN first_seen INTEGER", [])?;
+            conn.execute("UPDATE articles SET first_seen = pub_date", [])?;
+        }
+
         // 创建索引，提高查询性能
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_articles_feed_guid ON articles(feed_id, guid);",
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_articles_feed_link ON articles(feed_id, link);",
+            [],
+        )?;
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_articles_title ON articles(title);",
             [],
@@ -216,6 +521,79 @@ impl DbManager {
             [],
         )?;
 
+        // 创建RSS源地址变更历史表
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS feed_url_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                feed_id INTEGER NOT NULL REFERENCES feeds(id) ON DELETE CASCADE,
+                old_url TEXT NOT NULL,
+                new_url TEXT NOT NULL,
+                changed_at INTEGER NOT NULL
+            )
+            "#,
+            [],
+        )?;
+
+        // 创建文章历史版本表
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS article_revisions (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+                title TEXT NOT NULL,
+                content TEXT NOT NULL,
+                updated_at INTEGER,
+                replaced_at INTEGER NOT NULL
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_article_revisions_article_id ON article_revisions(article_id);",
+            [],
+        )?;
+
+        // 创建媒体附件表（播客节目的音频、视频）
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS enclosures (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                article_id INTEGER NOT NULL REFERENCES articles(id) ON DELETE CASCADE,
+                url TEXT NOT NULL,
+                mime_type TEXT,
+                length INTEGER,
+                duration INTEGER,
+                image TEXT,
+                episode INTEGER,
+                season INTEGER
+            )
+            "#,
+            [],
+        )?;
+        conn.execute(
+            "CREATE INDEX IF NOT EXISTS idx_enclosures_article_id ON enclosures(article_id);",
+            [],
+        )?;
+
+        // 创建WebSub推送订阅表，每个RSS源最多一个订阅
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS websub_subscriptions (
+                feed_id INTEGER PRIMARY KEY REFERENCES feeds(id) ON DELETE CASCADE,
+                hub TEXT NOT NULL,
+                topic TEXT NOT NULL,
+                token TEXT NOT NULL UNIQUE,
+                secret TEXT NOT NULL,
+                state TEXT NOT NULL DEFAULT 'pending',
+                lease_expires INTEGER,
+                last_requested INTEGER,
+                last_error TEXT
+            )
+            "#,
+            [],
+        )?;
+
         // 创建黑名单表
         conn.execute(
             r#"
@@ -227,6 +605,17 @@ impl DbManager {
             [],
         )?;
 
+        // 创建内部设置表，保存数据迁移等需要在启动之间保留的状态
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            )
+            "#,
+            [],
+        )?;
+
         // 创建触发器，自动更新FTS表
         conn.execute(
             r#"
@@ -247,6 +636,26 @@ impl DbManager {
             [],
         )?;
 
+        // 删除文章时同时删除其历史版本
+        conn.execute(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS article_revisions_ad AFTER DELETE ON articles BEGIN
+                DELETE FROM article_revisions WHERE article_id = old.id;
+            END;
+            "#,
+            [],
+        )?;
+
+        // 删除文章时同时删除其媒体附件
+        conn.execute(
+            r#"
+            CREATE TRIGGER IF NOT EXISTS enclosures_ad AFTER DELETE ON articles BEGIN
+                DELETE FROM enclosures WHERE article_id = old.id;
+            END;
+            "#,
+            [],
+        )?;
+
         conn.execute(
             r#"
             CREATE TRIGGER IF NOT EXISTS articles_au AFTER UPDATE ON articles BEGIN
@@ -273,15 +682,19 @@ impl DbManager {
         let tx = self.conn.transaction()?;
 
         let id = tx.query_row(
-            r#"INSERT INTO feeds (name, url, group_id, last_updated, translate_enabled, notification_enabled) 
-               VALUES (?, ?, ?, ?, ?, ?) RETURNING id"#,
+            r#"INSERT INTO feeds (name, url, group_id, last_updated, translate_enabled, notification_enabled, update_interval, always_use_browser, request_options, fetch_full_content) 
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?) RETURNING id"#,
             params![
                 feed.name.as_str(),
                 feed.url.as_str(),
                 group_id,
                 last_updated,
                 feed.translate_enabled,
-                feed.notification_enabled
+                feed.notification_enabled,
+                feed.update_interval,
+                feed.always_use_browser,
+                Self::request_options_to_json(&feed.request_options),
+                feed.fetch_full_content
             ],
             |row| row.get(0)
         )?;
@@ -294,7 +707,7 @@ impl DbManager {
 
     /// 获取所有RSS源
     pub fn get_all_feeds(&self) -> Result<Vec<Feed>> {
-        let mut stmt = self.conn.prepare("SELECT id, name, url, group_id, last_updated, translate_enabled, notification_enabled, last_update_status, update_attempts, next_retry_time FROM feeds ORDER BY name")?;
+        let mut stmt = self.conn.prepare("SELECT id, name, url, group_id, last_updated, translate_enabled, notification_enabled, last_update_status, update_attempts, next_retry_time, etag, last_modified, update_interval, last_error_kind, last_http_status, always_use_browser, request_options, fetch_full_content FROM feeds ORDER BY name")?;
         let feeds = stmt
             .query_map([], |row| {
                 let last_updated = row
@@ -314,20 +727,48 @@ impl DbManager {
                     last_update_status: row.get(7)?,
                     update_attempts: row.get(8)?,
                     next_retry_time,
+                    etag: row.get(10)?,
+                    last_modified: row.get(11)?,
+                    update_interval: row.get(12)?,
+                    last_error_kind: row.get(13)?,
+                    last_http_status: row.get(14)?,
+                    always_use_browser: row.get(15)?,
+                    request_options: Self::request_options_from_json(row.get(16)?),
+                    fetch_full_content: row.get(17)?,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
         Ok(feeds)
     }
 
-    /// 检查文章是否已存在且已有翻译内容
-    pub fn article_needs_translation(&self, feed_id: i64, link: &str) -> Result<bool> {
-        // 查询文章是否存在以及是否已有翻译标题
+    /// 检查文章是否已存在
+    pub fn article_exists(&self, article: &Article) -> Result<bool> {
+        Ok(self.find_existing_article(article)?.is_some())
+    }
+
+    /// 检查文章是否为新文章或已被发布者修改（需要重新获取全文）
+    pub fn article_needs_update(&self, article: &Article) -> Result<bool> {
+        match self.find_existing_article(article)? {
+            Some(id) => self.is_article_changed(id, article),
+            None => Ok(true),
+        }
+    }
+
+    /// 检查文章是否已存在且已有翻译内容，被修改的文章需要重新翻译
+    pub fn article_needs_translation(&self, article: &Article) -> Result<bool> {
+        let Some(id) = self.find_existing_article(article)? else {
+            return Ok(true);
+        };
+        if self.is_article_changed(id, article)? {
+            return Ok(true);
+        }
+
+        // 查询文章是否已有翻译标题
         let result = self
             .conn
             .query_row(
-                r#"SELECT translated_title FROM articles WHERE feed_id = ? AND link = ?"#,
-                params![feed_id, link],
+                r#"SELECT translated_title FROM articles WHERE id = ?"#,
+                params![id],
                 |row| {
                     let translated_title: Option<String> = row.get(0)?;
                     // 只检查标题是否需要翻译
@@ -347,21 +788,52 @@ impl DbManager {
         let categories_str = 
             serde_json::to_string(&article.categories).unwrap_or_else(|_| String::from("[]"));
 
-        // 首先尝试获取现有文章的ID
-        let existing_id = self
-            .conn
-            .query_row(
-                r#"SELECT id, translated_title FROM articles WHERE feed_id = ? AND link = ?"#,
-                params![article.feed_id, article.link.as_str()],
-                |row| {
-                    let id: i64 = row.get(0)?;
-                    let translated_title: Option<String> = row.get(1)?;
-                    Ok((id, translated_title))
-                },
-            )
-            .ok();
+        // 首先尝试获取现有文章的ID（按guid、链接或内容哈希匹配）
+        let existing_id = match self.find_existing_article(article)? {
+            Some(id) => self
+                .conn
+                .query_row(
+                    r#"SELECT id, translated_title FROM articles WHERE id = ?"#,
+                    params![id],
+                    |row| {
+                        let id: i64 = row.get(0)?;
+                        let translated_title: Option<String> = row.get(1)?;
+                        Ok((id, translated_title))
+                    },
+                )
+                .ok(),
+            None => None,
+        };
 
         if let Some((id, existing_translated_title)) = existing_id {
+            // 迁移前保存的文章按链接匹配到，补充guid
+            if let Some(guid) = Self::article_guid(article) {
+                self.conn.execute(
+                    "UPDATE articles SET guid = ? WHERE id = ? AND guid IS NULL",
+                    params![guid, id],
+                )?;
+            }
+
+            // 发布者修改了文章，保存旧版本后更新为新内容
+            self.save_enclosures(id, &article.enclosures)?;
+            if self.is_article_changed(id, article)? {
+                self.revise_article(id, article, &categories_str)?;
+                println!("文章已被修改，已保存旧版本: {}", article.title);
+                return Ok(false);
+            }
+
+            // 旧版本保存的文章补充哈希，用于之后检测修改；
+            // 内容与已保存的版本不同但尚未确认修改时，记录新哈希等待下次更新确认
+            let hash = Self::revision_hash(article);
+            self.conn.execute(
+                r#"UPDATE articles
+                   SET pending_hash = CASE WHEN content_hash IS NULL OR content_hash = ?1
+                                         THEN NULL ELSE ?1 END,
+                       content_hash = COALESCE(content_hash, ?1)
+                   WHERE id = ?2"#,
+                params![hash, id],
+            )?;
+
             // 文章已存在，检查是否需要更新翻译内容
             // 如果现有文章没有翻译，而新文章有翻译，或者新文章的翻译内容与现有不同，则更新
             let needs_translation_update = existing_translated_title.is_none() 
@@ -389,8 +861,8 @@ impl DbManager {
 
         // 文章不存在，插入新文章
         let _rows_affected = self.conn.execute(
-            r#"INSERT INTO articles (feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content) 
-               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
+            r#"INSERT INTO articles (feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content, summary, guid, updated_at, content_hash, first_seen) 
+               VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"#,
             params![
                 article.feed_id,
                 article.title.as_str(),
@@ -403,14 +875,266 @@ impl DbManager {
                 article.author.as_deref(),
                 categories_str.as_str(),
                 article.translated_title.as_deref(),
-                article.translated_content.as_deref()
+                article.translated_content.as_deref(),
+                article.summary.as_deref(),
+                Self::article_guid(article),
+                article.updated.map(|updated| updated.timestamp()),
+                Self::revision_hash(article),
+                article.first_seen.unwrap_or_else(Utc::now).timestamp()
             ],
         )?;
+        self.save_enclosures(self.conn.last_insert_rowid(), &article.enclosures)?;
 
         // 返回是否成功添加了新文章
         Ok(true)
     }
 
+    /// 保存文章的媒体附件，已保存过的附件（按地址判断）不重复添加
+    fn save_enclosures(&self, article_id: i64, enclosures: &[Enclosure]) -> Result<()> {
+        for enclosure in enclosures {
+            self.conn.execute(
+                r#"INSERT INTO enclosures (article_id, url, mime_type, length, duration, image, episode, season)
+                   SELECT ?, ?, ?, ?, ?, ?, ?, ?
+                   WHERE NOT EXISTS (SELECT 1 FROM enclosures WHERE article_id = ? AND url = ?)"#,
+                params![
+                    article_id,
+                    enclosure.url.as_str(),
+                    enclosure.mime_type.as_deref(),
+                    enclosure.length,
+                    enclosure.duration,
+                    enclosure.image.as_deref(),
+                    enclosure.episode,
+                    enclosure.season,
+                    article_id,
+                    enclosure.url.as_str()
+                ],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// 为文章列表加载媒体附件
+    fn load_enclosures<'a>(
+        &self,
+        articles: impl IntoIterator<Item = &'a mut Article>,
+    ) -> Result<()> {
+        let mut articles: Vec<&mut Article> = articles.into_iter().collect();
+        if articles.is_empty() {
+            return Ok(());
+        }
+
+        let ids = articles
+            .iter()
+            .map(|article| article.id.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut stmt = self.conn.prepare(&format!(
+            r#"SELECT id, article_id, url, mime_type, length, duration, image, episode, season
+               FROM enclosures WHERE article_id IN ({}) ORDER BY id"#,
+            ids
+        ))?;
+        let enclosures = stmt
+            .query_map([], |row| {
+                Ok(Enclosure {
+                    id: row.get(0)?,
+                    article_id: row.get(1)?,
+                    url: row.get(2)?,
+                    mime_type: row.get(3)?,
+                    length: row.get(4)?,
+                    duration: row.get(5)?,
+                    image: row.get(6)?,
+                    episode: row.get(7)?,
+                    season: row.get(8)?,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        for enclosure in enclosures {
+            if let Some(article) = articles
+                .iter_mut()
+                .find(|article| article.id == enclosure.article_id)
+            {
+                article.enclosures.push(enclosure);
+            }
+        }
+        Ok(())
+    }
+
+    /// 获取RSS源中带有媒体附件的文章（播客节目），按发布时间从新到旧排列
+    pub fn get_episodes_by_feed(
+        &self,
+        feed_id: i64,
+        limit: u32,
+        offset: u32,
+    ) -> Result<Vec<Article>> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content, summary, guid, updated_at, first_seen
+               FROM articles a
+               WHERE feed_id = ? AND EXISTS (SELECT 1 FROM enclosures e WHERE e.article_id = a.id)
+               ORDER BY pub_date DESC LIMIT ? OFFSET ?"#,
+        )?;
+        let mut articles = stmt
+            .query_map(params![feed_id, limit, offset], |row| {
+                let pub_date = Utc
+                    .timestamp_opt(row.get::<_, i64>(4)?, 0)
+                    .single()
+                    .unwrap_or(Utc::now());
+                let categories_str: Option<String> = row.get(10)?;
+                let categories: Vec<String> = categories_str
+                    .as_deref()
+                    .and_then(|s| serde_json::from_str(s).ok())
+                    .unwrap_or_default();
+                Ok(Article {
+                    id: row.get::<_, i64>(0)?,
+                    feed_id: row.get::<_, i64>(1)?,
+                    title: row.get(2)?,
+                    content: row.get(3)?,
+                    pub_date,
+                    link: row.get(5)?,
+                    is_read: row.get(6)?,
+                    is_favorite: row.get(7)?,
+                    thumbnail: row.get(8)?,
+                    author: row.get(9)?,
+                    categories,
+                    translated_title: row.get(11)?,
+                    translated_content: row.get(12)?,
+                    summary: row.get(13)?,
+                    guid: row.get(14)?,
+                    updated: row
+                        .get::<_, Option<i64>>(15)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    first_seen: row
+                        .get::<_, Option<i64>>(16)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    enclosures: Vec::new(),
+                    source_hash: None,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        self.load_enclosures(&mut articles)?;
+        Ok(articles)
+    }
+
+    /// 按链接规范化规则重写已保存的文章链接，返回修改的文章数量
+    ///
+    /// 用于去除旧文章链接中的跟踪参数，以及修改规则后使已有文章与新获取的文章链接一致
+    pub fn canonicalize_article_links(&mut self, link_rules: &LinkRules) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let rows = {
+            let mut stmt = tx.prepare(
+                r#"SELECT a.id, a.link, f.url FROM articles a
+                   JOIN feeds f ON a.feed_id = f.id
+                   WHERE a.link != ''"#,
+            )?;
+            stmt.query_map([], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>>>()?
+        };
+
+        let mut updated = 0;
+        for (id, link, feed_url) in rows {
+            let canonical = link_rules.canonicalize(&link, Some(&feed_url));
+            if canonical != link {
+                tx.execute(
+                    "UPDATE articles SET link = ? WHERE id = ?",
+                    params![canonical, id],
+                )?;
+                updated += 1;
+            }
+        }
+        tx.commit()?;
+        Ok(updated)
+    }
+
+    /// 链接规则变化时按新规则重写已保存的文章链接，返回修改的文章数量
+    ///
+    /// 规则的指纹保存在settings表中，升级后首次启动和修改规则后各执行一次
+    pub fn canonicalize_article_links_if_changed(
+        &mut self,
+        link_rules: &LinkRules,
+    ) -> Result<usize> {
+        let fingerprint = link_rules.fingerprint();
+        if self.get_setting(LINK_RULES_SETTING)?.as_deref() == Some(fingerprint.as_str()) {
+            return Ok(0);
+        }
+        let updated = self.canonicalize_article_links(link_rules)?;
+        self.set_setting(LINK_RULES_SETTING, &fingerprint)?;
+        Ok(updated)
+    }
+
+    /// 读取内部设置
+    fn get_setting(&self, key: &str) -> Result<Option<String>> {
+        self.conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()
+    }
+
+    /// 保存内部设置
+    fn set_setting(&self, key: &str, value: &str) -> Result<()> {
+        self.conn.execute(
+            "INSERT INTO settings (key, value) VALUES (?, ?)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value",
+            params![key, value],
+        )?;
+        Ok(())
+    }
+
+    /// 获取文章的历史版本，按替换时间从新到旧排列
+    pub fn get_article_revisions(&self, article_id: i64) -> Result<Vec<ArticleRevision>> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT id, article_id, title, content, updated_at, replaced_at
+               FROM article_revisions WHERE article_id = ?
+               ORDER BY replaced_at DESC, id DESC"#,
+        )?;
+        let revisions = stmt
+            .query_map(params![article_id], |row| {
+                Ok(ArticleRevision {
+                    id: row.get(0)?,
+                    article_id: row.get(1)?,
+                    title: row.get(2)?,
+                    content: row.get(3)?,
+                    updated: row
+                        .get::<_, Option<i64>>(4)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    replaced_at: Utc
+                        .timestamp_opt(row.get::<_, i64>(5)?, 0)
+                        .single()
+                        .unwrap_or(Utc::now()),
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(revisions)
+    }
+
+    /// 获取文章某个版本的标题和内容，revision_id为None时返回文章的当前内容
+    pub fn get_article_version(
+        &self,
+        article_id: i64,
+        revision_id: Option<i64>,
+    ) -> Result<(String, String)> {
+        match revision_id {
+            Some(revision_id) => self.conn.query_row(
+                "SELECT title, content FROM article_revisions WHERE id = ? AND article_id = ?",
+                params![revision_id, article_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ),
+            None => self.conn.query_row(
+                "SELECT title, content FROM articles WHERE id = ?",
+                params![article_id],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            ),
+        }
+    }
+
     /// 获取未读文章数量
     pub fn get_unread_count(&self, feed_id: Option<i64>) -> Result<u32> {
         match feed_id {
@@ -667,7 +1391,7 @@ impl DbManager {
         // 当feed_id为Some(id)时，条件会匹配特定feed_id的文章
         let mut stmt = self.conn.prepare(
             r#"
-            SELECT a.id, a.feed_id, a.title, a.content, a.pub_date, a.link, a.is_read, a.is_favorite, a.thumbnail, a.author, a.categories, a.translated_title, a.translated_content, f.name as feed_name 
+            SELECT a.id, a.feed_id, a.title, a.content, a.pub_date, a.link, a.is_read, a.is_favorite, a.thumbnail, a.author, a.categories, a.translated_title, a.translated_content, a.summary, a.guid, a.updated_at, a.first_seen, f.name as feed_name 
             FROM articles a
             JOIN feeds f ON a.feed_id = f.id
             JOIN articles_fts ft ON a.id = ft.rowid
@@ -704,13 +1428,24 @@ impl DbManager {
                 categories,
                 translated_title: row.get(11)?,
                 translated_content: row.get(12)?,
+                summary: row.get(13)?,
+                guid: row.get(14)?,
+                updated: row
+                    .get::<_, Option<i64>>(15)?
+                    .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                first_seen: row
+                    .get::<_, Option<i64>>(16)?
+                    .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                enclosures: Vec::new(),
+                source_hash: None,
             };
 
-            let feed_name: String = row.get(13)?;
+            let feed_name: String = row.get(17)?;
             Ok((article, feed_name))
         })?;
 
-        let results = results.collect::<Result<Vec<_>>>()?;
+        let mut results = results.collect::<Result<Vec<_>>>()?;
+        self.load_enclosures(results.iter_mut().map(|(article, _)| article))?;
 
         Ok(results)
     }
@@ -730,15 +1465,20 @@ impl DbManager {
 
     /// 更新RSS源
     pub fn update_feed(&mut self, feed: &Feed) -> Result<()> {
-        let last_updated_ts = feed.last_updated.map(|t| t.timestamp());
-
-        let last_updated = last_updated_ts.unwrap_or(0);
+        // 前端编辑时不传入最后更新时间，此时保留原值
+        let last_updated = feed.last_updated.map(|t| t.timestamp());
 
         // 开始事务
         let tx = self.conn.transaction()?;
 
+        // URL变更后，旧的缓存验证头不再有效
+        tx.execute(
+            "UPDATE feeds SET etag = NULL, last_modified = NULL WHERE id = ? AND url <> ?",
+            params![feed.id, feed.url.as_str()],
+        )?;
+
         tx.execute(
-            r#"UPDATE feeds SET name = ?, url = ?, group_id = ?, last_updated = ?, translate_enabled = ?, notification_enabled = ? 
+            r#"UPDATE feeds SET name = ?, url = ?, group_id = ?, last_updated = COALESCE(?, last_updated), translate_enabled = ?, notification_enabled = ?, update_interval = ?, always_use_browser = ?, request_options = ?, fetch_full_content = ? 
                WHERE id = ?"#,
             params![
                 feed.name.as_str(),
@@ -747,6 +1487,10 @@ impl DbManager {
                 last_updated,
                 feed.translate_enabled,
                 feed.notification_enabled,
+                feed.update_interval,
+                feed.always_use_browser,
+                Self::request_options_to_json(&feed.request_options),
+                feed.fetch_full_content,
                 feed.id
             ],
         )?;
@@ -780,15 +1524,23 @@ impl DbManager {
     }
 
     /// 更新RSS源的更新成功状态
-    pub fn update_feed_success(&mut self, feed_id: i64, last_updated: DateTime<Utc>) -> Result<()> {
+    ///
+    /// `next_update_time`为源通过TTL等提示给出的最早下次拉取时间，为None时下个周期正常更新
+    pub fn update_feed_success(
+        &mut self,
+        feed_id: i64,
+        last_updated: DateTime<Utc>,
+        next_update_time: Option<DateTime<Utc>>,
+    ) -> Result<()> {
         let last_updated_ts = last_updated.timestamp();
+        let next_update_time_ts = next_update_time.map(|dt| dt.timestamp());
 
         // 开始事务
         let tx = self.conn.transaction()?;
 
         tx.execute(
-            r#"UPDATE feeds SET last_updated = ?, last_update_status = 'success', update_attempts = 0, next_retry_time = NULL WHERE id = ?"#,
-            params![last_updated_ts, feed_id],
+            r#"UPDATE feeds SET last_updated = ?, last_update_status = 'success', update_attempts = 0, next_retry_time = ?, last_error_kind = NULL, last_http_status = NULL, failing_since = NULL WHERE id = ?"#,
+            params![last_updated_ts, next_update_time_ts, feed_id],
         )?;
 
         // 提交事务
@@ -797,8 +1549,156 @@ impl DbManager {
         Ok(())
     }
 
+    /// 更新RSS源的HTTP缓存验证头（ETag/Last-Modified）
+    pub fn update_feed_cache_headers(
+        &mut self,
+        feed_id: i64,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> Result<()> {
+        // 开始事务
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            r#"UPDATE feeds SET etag = ?, last_modified = ? WHERE id = ?"#,
+            params![etag, last_modified, feed_id],
+        )?;
+
+        // 提交事务
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// 保存RSS源声明的WebSub推送中心
+    ///
+    /// 首次发现时生成回调标识和签名密钥；推送中心或主题变化时保留回调标识，订阅状态重置为pending
+    pub fn save_websub_hub(&self, feed_id: i64, hub: &str, topic: &str) -> Result<()> {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        let secret = format!(
+            "{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        self.conn.execute(
+            r#"INSERT INTO websub_subscriptions (feed_id, hub, topic, token, secret)
+               VALUES (?, ?, ?, ?, ?)
+               ON CONFLICT(feed_id) DO UPDATE SET
+                   hub = excluded.hub,
+                   topic = excluded.topic,
+                   state = 'pending',
+                   lease_expires = NULL,
+                   last_requested = NULL,
+                   last_error = NULL
+               WHERE hub <> excluded.hub OR topic <> excluded.topic"#,
+            params![feed_id, hub, topic, token, secret],
+        )?;
+        Ok(())
+    }
+
+    /// 从查询结果构建WebSub订阅，列顺序见WEBSUB_COLUMNS
+    fn websub_subscription_from_row(row: &rusqlite::Row) -> Result<WebSubSubscription> {
+        let timestamp = |index: usize| -> Result<Option<DateTime<Utc>>> {
+            Ok(row
+                .get::<_, Option<i64>>(index)?
+                .and_then(|ts| Utc.timestamp_opt(ts, 0).single()))
+        };
+        Ok(WebSubSubscription {
+            feed_id: row.get(0)?,
+            hub: row.get(1)?,
+            topic: row.get(2)?,
+            token: row.get(3)?,
+            secret: row.get(4)?,
+            state: row.get(5)?,
+            lease_expires: timestamp(6)?,
+            last_requested: timestamp(7)?,
+            last_error: row.get(8)?,
+        })
+    }
+
+    /// 获取所有WebSub订阅
+    pub fn get_websub_subscriptions(&self) -> Result<Vec<WebSubSubscription>> {
+        let mut stmt = self.conn.prepare(&format!(
+            "SELECT {} FROM websub_subscriptions ORDER BY feed_id",
+            WEBSUB_COLUMNS
+        ))?;
+        let subscriptions = stmt
+            .query_map([], Self::websub_subscription_from_row)?
+            .collect::<Result<Vec<_>>>()?;
+        Ok(subscriptions)
+    }
+
+    /// 根据回调标识获取WebSub订阅
+    pub fn get_websub_subscription_by_token(
+        &self,
+        token: &str,
+    ) -> Result<Option<WebSubSubscription>> {
+        self.conn
+            .query_row(
+                &format!(
+                    "SELECT {} FROM websub_subscriptions WHERE token = ?",
+                    WEBSUB_COLUMNS
+                ),
+                params![token],
+                Self::websub_subscription_from_row,
+            )
+            .optional()
+    }
+
+    /// 记录发送订阅请求的时间和结果，error为None表示推送中心已接受请求
+    pub fn update_websub_request(
+        &self,
+        feed_id: i64,
+        requested_at: DateTime<Utc>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        self.conn.execute(
+            "UPDATE websub_subscriptions SET last_requested = ?, last_error = ? WHERE feed_id = ?",
+            params![requested_at.timestamp(), error, feed_id],
+        )?;
+        Ok(())
+    }
+
+    /// 推送中心确认订阅后标记为生效，并保存到期时间
+    pub fn activate_websub_subscription(
+        &self,
+        token: &str,
+        lease_expires: Option<DateTime<Utc>>,
+    ) -> Result<()> {
+        self.conn.execute(
+            r#"UPDATE websub_subscriptions SET state = 'active', lease_expires = ?, last_error = NULL
+               WHERE token = ?"#,
+            params![lease_expires.map(|t| t.timestamp()), token],
+        )?;
+        Ok(())
+    }
+
+    /// 推送中心拒绝订阅，保存拒绝原因
+    pub fn deny_websub_subscription(&self, token: &str, reason: Option<&str>) -> Result<()> {
+        self.conn.execute(
+            r#"UPDATE websub_subscriptions SET state = 'denied', lease_expires = NULL, last_error = ?
+               WHERE token = ?"#,
+            params![reason.unwrap_or("推送中心拒绝订阅"), token],
+        )?;
+        Ok(())
+    }
+
+    /// 获取订阅生效且未到期的RSS源ID
+    pub fn get_active_websub_feed_ids(&self, now: DateTime<Utc>) -> Result<HashSet<i64>> {
+        let mut stmt = self.conn.prepare(
+            r#"SELECT feed_id FROM websub_subscriptions
+               WHERE state = 'active' AND (lease_expires IS NULL OR lease_expires > ?)"#,
+        )?;
+        let feed_ids = stmt
+            .query_map(params![now.timestamp()], |row| row.get(0))?
+            .collect::<Result<HashSet<i64>>>()?;
+        Ok(feed_ids)
+    }
+
     /// 更新RSS源的更新失败状态，并计算下次重试时间
-    pub fn update_feed_failure(&mut self, feed_id: i64, error_message: &str) -> Result<()> {
+    ///
+    /// 保存错误信息、错误类型和HTTP状态码；服务器通过Retry-After指定了重试时间时，按该时间重试
+    pub fn update_feed_failure(&mut self, feed_id: i64, error: &AppError) -> Result<()> {
         // 获取当前尝试次数
         let current_attempts: i32 = self.conn.query_row(
             r#"SELECT update_attempts FROM feeds WHERE id = ?"#,
@@ -806,17 +1706,28 @@ impl DbManager {
             |row| row.get(0),
         )?;
 
-        // 计算下次重试时间：使用指数退避策略
-        let retry_delay_seconds = 2_u64.pow(current_attempts as u32) * 60;
-        let next_retry_time = Utc::now() + chrono::Duration::seconds(retry_delay_seconds as i64);
+        // 计算下次重试时间：优先使用服务器指定的Retry-After，否则使用指数退避策略
+        let now = Utc::now();
+        let next_retry_time = error.retry_after().unwrap_or_else(|| {
+            let retry_delay_seconds = 2_u64.pow(current_attempts as u32) * 60;
+            now + chrono::Duration::seconds(retry_delay_seconds as i64)
+        });
         let next_retry_time_ts = next_retry_time.timestamp();
 
         // 开始事务
         let tx = self.conn.transaction()?;
 
+        // failing_since只在开始连续失败时记录
         tx.execute(
-            r#"UPDATE feeds SET last_update_status = ?, update_attempts = update_attempts + 1, next_retry_time = ? WHERE id = ?"#,
-            params![error_message, next_retry_time_ts, feed_id],
+            r#"UPDATE feeds SET last_update_status = ?, last_error_kind = ?, last_http_status = ?, update_attempts = update_attempts + 1, next_retry_time = ?, failing_since = COALESCE(failing_since, ?) WHERE id = ?"#,
+            params![
+                error.to_string(),
+                error.kind(),
+                error.status(),
+                next_retry_time_ts,
+                now.timestamp(),
+                feed_id
+            ],
         )?;
 
         // 提交事务
@@ -825,6 +1736,210 @@ impl DbManager {
         Ok(())
     }
 
+    /// 处理RSS源的永久重定向，将源地址更新为新地址并记录变更历史
+    ///
+    /// 新地址已被其他源订阅时，将文章合并到该源并删除旧源。返回处理后的源ID
+    pub fn apply_feed_redirect(&mut self, feed_id: i64, new_url: &str) -> Result<i64> {
+        let now_ts = Utc::now().timestamp();
+
+        // 开始事务
+        let tx = self.conn.transaction()?;
+
+        let old_url: String = tx.query_row(
+            "SELECT url FROM feeds WHERE id = ?",
+            params![feed_id],
+            |row| row.get(0),
+        )?;
+
+        let existing_id: Option<i64> = tx
+            .query_row(
+                "SELECT id FROM feeds WHERE url = ? AND id <> ?",
+                params![new_url, feed_id],
+                |row| row.get(0),
+            )
+            .ok();
+
+        let target_id = match existing_id {
+            None => {
+                // 直接更新地址，旧的缓存验证头不再有效
+                tx.execute(
+                    "UPDATE feeds SET url = ?, etag = NULL, last_modified = NULL WHERE id = ?",
+                    params![new_url, feed_id],
+                )?;
+                feed_id
+            }
+            Some(target_id) => {
+                // 合并到已存在的源：重复的文章保留已读和收藏状态
+                tx.execute(
+                    r#"UPDATE articles SET
+                         is_read = is_read OR EXISTS (
+                           SELECT 1 FROM articles old WHERE old.feed_id = ?2 AND old.is_read
+                             AND ((old.link <> '' AND old.link = articles.link) OR old.guid = articles.guid)),
+                         is_favorite = is_favorite OR EXISTS (
+                           SELECT 1 FROM articles old WHERE old.feed_id = ?2 AND old.is_favorite
+                             AND ((old.link <> '' AND old.link = articles.link) OR old.guid = articles.guid))
+                       WHERE feed_id = ?1"#,
+                    params![target_id, feed_id],
+                )?;
+                // 移动目标源中不存在的文章，其余随旧源一起删除
+                tx.execute(
+                    r#"UPDATE articles SET feed_id = ?1
+                       WHERE feed_id = ?2
+                         AND (link = '' OR link NOT IN (SELECT link FROM articles WHERE feed_id = ?1))
+                         AND (guid IS NULL OR guid NOT IN (SELECT guid FROM articles WHERE feed_id = ?1 AND guid IS NOT NULL))"#,
+                    params![target_id, feed_id],
+                )?;
+                // 同步全文索引中已移动到目标源的文章
+                tx.execute(
+                    "UPDATE articles_fts SET feed_id = ?1 WHERE rowid IN (SELECT id FROM articles WHERE feed_id = ?1)",
+                    params![target_id],
+                )?;
+                tx.execute("DELETE FROM articles WHERE feed_id = ?", params![feed_id])?;
+                tx.execute(
+                    "UPDATE feed_url_history SET feed_id = ? WHERE feed_id = ?",
+                    params![target_id, feed_id],
+                )?;
+                tx.execute("DELETE FROM feeds WHERE id = ?", params![feed_id])?;
+                target_id
+            }
+        };
+
+        tx.execute(
+            "INSERT INTO feed_url_history (feed_id, old_url, new_url, changed_at) VALUES (?, ?, ?, ?)",
+            params![target_id, old_url, new_url, now_ts],
+        )?;
+
+        // 提交事务
+        tx.commit()?;
+
+        Ok(target_id)
+    }
+
+    /// 重置RSS源的重试状态（手动刷新时使用），保留失败记录以便统计健康状态
+    pub fn reset_feed_retry(&mut self, feed_id: i64) -> Result<()> {
+        // 开始事务
+        let tx = self.conn.transaction()?;
+
+        tx.execute(
+            r#"UPDATE feeds SET update_attempts = 0, next_retry_time = NULL WHERE id = ?"#,
+            params![feed_id],
+        )?;
+
+        // 提交事务
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// 获取所有RSS源的健康报告
+    ///
+    /// 持续失败超过`dead_after_days`天的源标记为dead，可供批量取消订阅
+    pub fn get_feed_health(&self, dead_after_days: i64) -> Result<Vec<FeedHealth>> {
+        // 统计发文频率的时间窗口（周）
+        const WINDOW_WEEKS: i64 = 12;
+        // 超过该天数没有新文章视为stale
+        const STALE_AFTER_DAYS: i64 = 90;
+
+        let now = Utc::now();
+        let window_start = now - chrono::Duration::weeks(WINDOW_WEEKS);
+
+        let mut stmt = self.conn.prepare(
+            r#"SELECT f.id, f.name, f.url, f.last_updated, f.update_attempts, f.last_update_status, f.last_error_kind, f.last_http_status, f.next_retry_time, f.failing_since,
+                      MAX(a.pub_date), COALESCE(SUM(CASE WHEN a.pub_date >= ? THEN 1 ELSE 0 END), 0)
+               FROM feeds f LEFT JOIN articles a ON a.feed_id = f.id
+               GROUP BY f.id ORDER BY f.name"#,
+        )?;
+
+        let to_datetime = |ts: Option<i64>| ts.and_then(|ts| Utc.timestamp_opt(ts, 0).single());
+
+        let health = stmt
+            .query_map(params![window_start.timestamp()], |row| {
+                let update_attempts: i32 = row.get(4)?;
+                let last_update_status: Option<String> = row.get(5)?;
+                let failing_since = to_datetime(row.get(9)?);
+                let newest_article_date = to_datetime(row.get(10)?);
+                let recent_count: i64 = row.get(11)?;
+
+                let status = if failing_since
+                    .is_some_and(|since| now - since >= chrono::Duration::days(dead_after_days))
+                {
+                    FeedHealthStatus::Dead
+                } else if update_attempts > 0 || failing_since.is_some() {
+                    FeedHealthStatus::Failing
+                } else if newest_article_date
+                    .is_none_or(|date| now - date >= chrono::Duration::days(STALE_AFTER_DAYS))
+                {
+                    FeedHealthStatus::Stale
+                } else {
+                    FeedHealthStatus::Healthy
+                };
+
+                Ok(FeedHealth {
+                    feed_id: row.get(0)?,
+                    name: row.get(1)?,
+                    url: row.get(2)?,
+                    last_success_time: to_datetime(
+                        row.get::<_, Option<i64>>(3)?.filter(|ts| *ts > 0),
+                    ),
+                    update_attempts,
+                    last_error: last_update_status.filter(|status| status != "success"),
+                    last_error_kind: row.get(6)?,
+                    last_http_status: row.get(7)?,
+                    next_retry_time: to_datetime(row.get(8)?),
+                    failing_since,
+                    articles_per_week: recent_count as f64 / WINDOW_WEEKS as f64,
+                    newest_article_date,
+                    status,
+                })
+            })?
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(health)
+    }
+
+    /// 批量删除RSS源
+    pub fn delete_feeds(&mut self, feed_ids: &[i64]) -> Result<()> {
+        // 开始事务
+        let tx = self.conn.transaction()?;
+
+        for feed_id in feed_ids {
+            tx.execute("DELETE FROM feeds WHERE id = ?", params![feed_id])?;
+        }
+
+        // 提交事务
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// 统计各RSS源的平均发文间隔（秒），返回HashMap<feed_id, interval>
+    ///
+    /// 只统计`since`之后发布的文章，文章少于两篇的源不包含在结果中
+    pub fn get_feed_posting_intervals(
+        &self,
+        since: DateTime<Utc>,
+    ) -> Result<std::collections::HashMap<i64, i64>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT feed_id, COUNT(*), MIN(pub_date), MAX(pub_date) FROM articles WHERE pub_date >= ? GROUP BY feed_id HAVING COUNT(*) >= 2"
+        )?;
+
+        let rows = stmt.query_map(params![since.timestamp()], |row| {
+            let feed_id: i64 = row.get(0)?;
+            let count: i64 = row.get(1)?;
+            let first_pub_date: i64 = row.get(2)?;
+            let last_pub_date: i64 = row.get(3)?;
+            Ok((feed_id, (last_pub_date - first_pub_date) / (count - 1)))
+        })?;
+
+        let mut result = std::collections::HashMap::new();
+        for row in rows {
+            let (feed_id, interval) = row?;
+            result.insert(feed_id, interval);
+        }
+
+        Ok(result)
+    }
+
     /// 添加分组
     pub fn add_group(&mut self, group: &FeedGroup) -> Result<i64> {
         // 开始事务
@@ -897,7 +2012,7 @@ impl DbManager {
 
     /// 获取特定分组的RSS源
     pub fn get_feeds_by_group(&self, group_id: i64) -> Result<Vec<Feed>> {
-        let mut stmt = self.conn.prepare("SELECT id, name, url, group_id, last_updated, translate_enabled, notification_enabled, last_update_status, update_attempts, next_retry_time FROM feeds WHERE group_id = ? ORDER BY name")?;
+        let mut stmt = self.conn.prepare("SELECT id, name, url, group_id, last_updated, translate_enabled, notification_enabled, last_update_status, update_attempts, next_retry_time, etag, last_modified, update_interval, last_error_kind, last_http_status, always_use_browser, request_options, fetch_full_content FROM feeds WHERE group_id = ? ORDER BY name")?;
         let feeds = stmt
             .query_map(params![group_id], |row| {
                 let last_updated = row
@@ -917,6 +2032,14 @@ impl DbManager {
                     last_update_status: row.get(7)?,
                     update_attempts: row.get(8)?,
                     next_retry_time,
+                    etag: row.get(10)?,
+                    last_modified: row.get(11)?,
+                    update_interval: row.get(12)?,
+                    last_error_kind: row.get(13)?,
+                    last_http_status: row.get(14)?,
+                    always_use_browser: row.get(15)?,
+                    request_options: Self::request_options_from_json(row.get(16)?),
+                    fetch_full_content: row.get(17)?,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
@@ -925,7 +2048,7 @@ impl DbManager {
 
     /// 根据ID获取RSS源
     pub fn get_feed_by_id(&self, feed_id: i64) -> Result<Feed> {
-        let mut stmt = self.conn.prepare("SELECT id, name, url, group_id, last_updated, translate_enabled, notification_enabled, last_update_status, update_attempts, next_retry_time FROM feeds WHERE id = ?")?;
+        let mut stmt = self.conn.prepare("SELECT id, name, url, group_id, last_updated, translate_enabled, notification_enabled, last_update_status, update_attempts, next_retry_time, etag, last_modified, update_interval, last_error_kind, last_http_status, always_use_browser, request_options, fetch_full_content FROM feeds WHERE id = ?")?;
         let feed = stmt.query_row(params![feed_id], |row| {
             let last_updated = row
                 .get::<_, Option<i64>>(4)?
@@ -944,6 +2067,14 @@ impl DbManager {
                 last_update_status: row.get(7)?,
                 update_attempts: row.get(8)?,
                 next_retry_time,
+                etag: row.get(10)?,
+                last_modified: row.get(11)?,
+                update_interval: row.get(12)?,
+                last_error_kind: row.get(13)?,
+                last_http_status: row.get(14)?,
+                always_use_browser: row.get(15)?,
+                request_options: Self::request_options_from_json(row.get(16)?),
+                fetch_full_content: row.get(17)?,
             })
         })?;
         Ok(feed)
@@ -956,8 +2087,8 @@ impl DbManager {
         limit: u32,
         offset: u32,
     ) -> Result<Vec<Article>> {
-        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content FROM articles WHERE feed_id = ? ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
-        let articles = stmt
+        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content, summary, guid, updated_at, first_seen FROM articles WHERE feed_id = ? ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
+        let mut articles = stmt
             .query_map(params![feed_id, limit, offset], |row| {
                 let pub_date = Utc
                     .timestamp_opt(row.get::<_, i64>(4)?, 0)
@@ -982,16 +2113,27 @@ impl DbManager {
                     categories,
                     translated_title: row.get(11)?,
                     translated_content: row.get(12)?,
+                    summary: row.get(13)?,
+                    guid: row.get(14)?,
+                    updated: row
+                        .get::<_, Option<i64>>(15)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    first_seen: row
+                        .get::<_, Option<i64>>(16)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    enclosures: Vec::new(),
+                    source_hash: None,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
+        self.load_enclosures(&mut articles)?;
         Ok(articles)
     }
 
     /// 获取所有文章
     pub fn get_all_articles(&self, limit: u32, offset: u32) -> Result<Vec<Article>> {
-        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content FROM articles ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
-        let articles = stmt
+        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content, summary, guid, updated_at, first_seen FROM articles ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
+        let mut articles = stmt
             .query_map(params![limit, offset], |row| {
                 let pub_date = Utc
                     .timestamp_opt(row.get::<_, i64>(4)?, 0)
@@ -1016,9 +2158,20 @@ impl DbManager {
                     categories,
                     translated_title: row.get(11)?,
                     translated_content: row.get(12)?,
+                    summary: row.get(13)?,
+                    guid: row.get(14)?,
+                    updated: row
+                        .get::<_, Option<i64>>(15)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    first_seen: row
+                        .get::<_, Option<i64>>(16)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    enclosures: Vec::new(),
+                    source_hash: None,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
+        self.load_enclosures(&mut articles)?;
         Ok(articles)
     }
 
@@ -1033,8 +2186,8 @@ impl DbManager {
 
     /// 获取收藏的文章
     pub fn get_favorite_articles(&self, limit: u32, offset: u32) -> Result<Vec<Article>> {
-        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content FROM articles WHERE is_favorite = TRUE ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
-        let articles = stmt
+        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content, summary, guid, updated_at, first_seen FROM articles WHERE is_favorite = TRUE ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
+        let mut articles = stmt
             .query_map(params![limit, offset], |row| {
                 let pub_date = Utc
                     .timestamp_opt(row.get::<_, i64>(4)?, 0)
@@ -1059,16 +2212,27 @@ impl DbManager {
                     categories,
                     translated_title: row.get(11)?,
                     translated_content: row.get(12)?,
+                    summary: row.get(13)?,
+                    guid: row.get(14)?,
+                    updated: row
+                        .get::<_, Option<i64>>(15)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    first_seen: row
+                        .get::<_, Option<i64>>(16)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    enclosures: Vec::new(),
+                    source_hash: None,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
+        self.load_enclosures(&mut articles)?;
         Ok(articles)
     }
 
     /// 获取未读文章
     pub fn get_unread_articles(&self, limit: u32, offset: u32) -> Result<Vec<Article>> {
-        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content FROM articles WHERE is_read = FALSE ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
-        let articles = stmt
+        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content, summary, guid, updated_at, first_seen FROM articles WHERE is_read = FALSE ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
+        let mut articles = stmt
             .query_map(params![limit, offset], |row| {
                 let pub_date = Utc
                     .timestamp_opt(row.get::<_, i64>(4)?, 0)
@@ -1093,9 +2257,20 @@ impl DbManager {
                     categories,
                     translated_title: row.get(11)?,
                     translated_content: row.get(12)?,
+                    summary: row.get(13)?,
+                    guid: row.get(14)?,
+                    updated: row
+                        .get::<_, Option<i64>>(15)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    first_seen: row
+                        .get::<_, Option<i64>>(16)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    enclosures: Vec::new(),
+                    source_hash: None,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
+        self.load_enclosures(&mut articles)?;
         Ok(articles)
     }
 
@@ -1106,8 +2281,8 @@ impl DbManager {
         limit: u32,
         offset: u32,
     ) -> Result<Vec<Article>> {
-        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content FROM articles WHERE feed_id = ? AND is_read = FALSE ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
-        let articles = stmt
+        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content, summary, guid, updated_at, first_seen FROM articles WHERE feed_id = ? AND is_read = FALSE ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
+        let mut articles = stmt
             .query_map(params![feed_id, limit, offset], |row| {
                 let pub_date = Utc
                     .timestamp_opt(row.get::<_, i64>(4)?, 0)
@@ -1132,9 +2307,20 @@ impl DbManager {
                     categories,
                     translated_title: row.get(11)?,
                     translated_content: row.get(12)?,
+                    summary: row.get(13)?,
+                    guid: row.get(14)?,
+                    updated: row
+                        .get::<_, Option<i64>>(15)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    first_seen: row
+                        .get::<_, Option<i64>>(16)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    enclosures: Vec::new(),
+                    source_hash: None,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
+        self.load_enclosures(&mut articles)?;
         Ok(articles)
     }
 
@@ -1145,8 +2331,8 @@ impl DbManager {
         limit: u32,
         offset: u32,
     ) -> Result<Vec<Article>> {
-        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content FROM articles WHERE feed_id = ? AND is_favorite = TRUE ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
-        let articles = stmt
+        let mut stmt = self.conn.prepare("SELECT id, feed_id, title, content, pub_date, link, is_read, is_favorite, thumbnail, author, categories, translated_title, translated_content, summary, guid, updated_at, first_seen FROM articles WHERE feed_id = ? AND is_favorite = TRUE ORDER BY pub_date DESC LIMIT ? OFFSET ?")?;
+        let mut articles = stmt
             .query_map(params![feed_id, limit, offset], |row| {
                 let pub_date = Utc
                     .timestamp_opt(row.get::<_, i64>(4)?, 0)
@@ -1171,9 +2357,20 @@ impl DbManager {
                     categories,
                     translated_title: row.get(11)?,
                     translated_content: row.get(12)?,
+                    summary: row.get(13)?,
+                    guid: row.get(14)?,
+                    updated: row
+                        .get::<_, Option<i64>>(15)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    first_seen: row
+                        .get::<_, Option<i64>>(16)?
+                        .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+                    enclosures: Vec::new(),
+                    source_hash: None,
                 })
             })?
             .collect::<Result<Vec<_>>>()?;
+        self.load_enclosures(&mut articles)?;
         Ok(articles)
     }
 
@@ -1243,6 +2440,8 @@ impl DbManager {
     }
 
     /// 导出OPML文件
+    ///
+    /// 只导出名称、地址和分组，自定义请求头、Cookie和认证信息等敏感设置不会导出
     pub fn export_opml(&self) -> Result<String> {
         // 获取所有分组和RSS源
         let groups = self.get_all_groups()?;
@@ -1278,7 +2477,7 @@ impl DbManager {
             let outline = Outline {
                 text: feed.name.clone(),
                 title: Some(feed.name.clone()),
-                xml_url: Some(feed.url.clone()),
+                xml_url: Some(Self::opml_feed_url(&feed.url)),
                 ..Default::default()
             };
             opml.body.outlines.push(outline);
@@ -1298,7 +2497,7 @@ impl DbManager {
                     let feed_outline = Outline {
                         text: feed.name.clone(),
                         title: Some(feed.name.clone()),
-                        xml_url: Some(feed.url.clone()),
+                        xml_url: Some(Self::opml_feed_url(&feed.url)),
                         ..Default::default()
                     };
                     group_outline.outlines.push(feed_outline);
@@ -1371,6 +2570,14 @@ impl DbManager {
                     last_update_status: None,
                     update_attempts: 0,
                     next_retry_time: None,
+                    etag: None,
+                    last_modified: None,
+                    update_interval: None,
+                    last_error_kind: None,
+                    last_http_status: None,
+                    always_use_browser: false,
+                    request_options: FeedRequestOptions::default(),
+                    fetch_full_content: false,
                 };
 
                 self.add_feed(&feed)?;
@@ -1614,3 +2821,235 @@ impl DbManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_db() -> (DbManager, i64) {
+        let db = DbManager::new(":memory:").unwrap();
+        let feed_id = db
+            .conn
+            .query_row(
+                "INSERT INTO feeds (name, url) VALUES ('test', 'https://example.com/feed') RETURNING id",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        (db, feed_id)
+    }
+
+    fn article(feed_id: i64, guid: Option<&str>, link: &str, content: &str) -> Article {
+        Article {
+            id: 0,
+            feed_id,
+            title: "标题".to_string(),
+            content: content.to_string(),
+            pub_date: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            link: link.to_string(),
+            is_read: false,
+            is_favorite: false,
+            thumbnail: None,
+            author: None,
+            categories: Vec::new(),
+            translated_title: None,
+            translated_content: None,
+            summary: None,
+            guid: guid.map(str::to_string),
+            updated: None,
+            first_seen: None,
+            enclosures: Vec::new(),
+            source_hash: None,
+        }
+    }
+
+    /// 插入旧版本保存的文章（没有guid）
+    fn insert_legacy(db: &DbManager, feed_id: i64, link: &str, content: &str) -> i64 {
+        db.conn
+            .query_row(
+                "INSERT INTO articles (feed_id, title, content, pub_date, link) VALUES (?, '标题', ?, 0, ?) RETURNING id",
+                params![feed_id, content, link],
+                |row| row.get(0),
+            )
+            .unwrap()
+    }
+
+    fn article_count(db: &DbManager) -> i64 {
+        db.conn
+            .query_row("SELECT COUNT(*) FROM articles", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn article_guid_prefers_source_guid() {
+        let with_guid = article(
+            1,
+            Some("  tag:example.com,2024:1 "),
+            "https://example.com/1",
+            "",
+        );
+        assert_eq!(
+            DbManager::article_guid(&with_guid).as_deref(),
+            Some("tag:example.com,2024:1")
+        );
+        // 没有guid但有链接时按链接匹配
+        assert_eq!(
+            DbManager::article_guid(&article(1, Some(" "), "https://example.com/1", "")),
+            None
+        );
+        // 既没有guid也没有链接时使用内容哈希
+        let first = DbManager::article_guid(&article(1, None, "", "<p>一</p>")).unwrap();
+        let second = DbManager::article_guid(&article(1, None, "", "<p>二</p>")).unwrap();
+        assert!(first.starts_with("hash:"));
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn finds_existing_article_by_guid_before_link() {
+        let (db, feed_id) = test_db();
+        let saved = article(feed_id, Some("a"), "https://example.com/1", "<p>内容</p>");
+        db.add_article(&saved).unwrap();
+        let id = db.find_existing_article(&saved).unwrap().unwrap();
+
+        // 链接变化时仍按guid匹配
+        let moved = article(
+            feed_id,
+            Some("a"),
+            "https://example.com/moved",
+            "<p>内容</p>",
+        );
+        assert_eq!(db.find_existing_article(&moved).unwrap(), Some(id));
+        // 链接相同但guid不同的是另一篇文章
+        let other = article(feed_id, Some("b"), "https://example.com/1", "<p>内容</p>");
+        assert_eq!(db.find_existing_article(&other).unwrap(), None);
+        // 没有guid时按链接匹配
+        let no_guid = article(feed_id, None, "https://example.com/1", "<p>内容</p>");
+        assert_eq!(db.find_existing_article(&no_guid).unwrap(), Some(id));
+    }
+
+    #[test]
+    fn legacy_article_matched_by_link_gets_guid() {
+        let (db, feed_id) = test_db();
+        let legacy_id = insert_legacy(&db, feed_id, "https://example.com/1", "<p>内容</p>");
+
+        let fetched = article(feed_id, Some("a"), "https://example.com/1", "<p>内容</p>");
+        assert_eq!(db.find_existing_article(&fetched).unwrap(), Some(legacy_id));
+        db.add_article(&fetched).unwrap();
+
+        let guid: Option<String> = db
+            .conn
+            .query_row(
+                "SELECT guid FROM articles WHERE id = ?",
+                params![legacy_id],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(guid.as_deref(), Some("a"));
+        assert_eq!(article_count(&db), 1);
+    }
+
+    #[test]
+    fn linkless_articles_are_keyed_by_content() {
+        let (db, feed_id) = test_db();
+        let first = article(feed_id, None, "", "<p>一</p>");
+        let second = article(feed_id, None, "", "<p>二</p>");
+        db.add_article(&first).unwrap();
+        db.add_article(&second).unwrap();
+        db.add_article(&first).unwrap();
+        assert_eq!(article_count(&db), 2);
+    }
+
+    #[test]
+    fn guid_migration_matches_sanitized_content() {
+        let (db, feed_id) = test_db();
+        // 旧版本保存的是清理HTML之前的内容
+        let raw = r#"<p onclick="track()">内容</p><script>alert(1)</script>"#;
+        let legacy_id = insert_legacy(&db, feed_id, "", raw);
+        DbManager::migrate_article_guids(&db.conn).unwrap();
+
+        // 新获取的同一篇文章内容已经过清理
+        let fetched = article(feed_id, None, "", &sanitize_html(raw));
+        assert_ne!(fetched.content, raw);
+        assert_eq!(db.find_existing_article(&fetched).unwrap(), Some(legacy_id));
+        db.add_article(&fetched).unwrap();
+        assert_eq!(article_count(&db), 1);
+    }
+
+    #[test]
+    fn feed_redirect_merge_keeps_read_and_favorite_flags() {
+        let (mut db, target_id) = test_db();
+        let old_id: i64 = db
+            .conn
+            .query_row(
+                "INSERT INTO feeds (name, url) VALUES ('old', 'http://example.com/rss') RETURNING id",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+
+        let mut read = article(old_id, None, "https://example.com/1", "");
+        read.is_read = true;
+        let mut favorite = article(old_id, Some("b"), "https://example.com/2", "");
+        favorite.is_favorite = true;
+        db.add_article(&read).unwrap();
+        db.add_article(&favorite).unwrap();
+        db.add_article(&article(old_id, None, "https://example.com/3", ""))
+            .unwrap();
+        db.add_article(&article(target_id, None, "https://example.com/1", ""))
+            .unwrap();
+        db.add_article(&article(
+            target_id,
+            Some("b"),
+            "https://example.com/2-moved",
+            "",
+        ))
+        .unwrap();
+
+        assert_eq!(
+            db.apply_feed_redirect(old_id, "https://example.com/feed")
+                .unwrap(),
+            target_id
+        );
+        let flags = |link: &str| -> (bool, bool) {
+            db.conn
+                .query_row(
+                    "SELECT is_read, is_favorite FROM articles WHERE feed_id = ? AND link = ?",
+                    params![target_id, link],
+                    |row| Ok((row.get(0)?, row.get(1)?)),
+                )
+                .unwrap()
+        };
+        assert_eq!(flags("https://example.com/1"), (true, false));
+        assert_eq!(flags("https://example.com/2-moved"), (false, true));
+        assert_eq!(flags("https://example.com/3"), (false, false));
+        assert_eq!(article_count(&db), 3);
+    }
+
+    #[test]
+    fn rewrites_article_links_only_when_rules_change() {
+        let (mut db, feed_id) = test_db();
+        let link = |db: &DbManager, id: i64| -> String {
+            db.conn
+                .query_row(
+                    "SELECT link FROM articles WHERE id = ?",
+                    params![id],
+                    |row| row.get(0),
+                )
+                .unwrap()
+        };
+        let first = insert_legacy(&db, feed_id, "https://example.com/1?utm_source=rss", "");
+
+        let rules = LinkRules::default();
+        assert_eq!(db.canonicalize_article_links_if_changed(&rules).unwrap(), 1);
+        assert_eq!(link(&db, first), "https://example.com/1");
+
+        // 规则未变化时不再处理
+        let second = insert_legacy(&db, feed_id, "https://example.com/2?campaign=spring", "");
+        assert_eq!(db.canonicalize_article_links_if_changed(&rules).unwrap(), 0);
+        assert_eq!(link(&db, second), "https://example.com/2?campaign=spring");
+
+        let rules = LinkRules::new(true, &["campaign".to_string()], &[]);
+        assert_eq!(db.canonicalize_article_links_if_changed(&rules).unwrap(), 1);
+        assert_eq!(link(&db, second), "https://example.com/2");
+    }
+}
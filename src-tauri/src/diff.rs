@@ -0,0 +1,249 @@
+use crate::models::{DiffKind, DiffLine};
+use scraper::{ElementRef, Html, Node};
+
+/// 分段时视为块级元素的标签
+const BLOCK_TAGS: &[&str] = &[
+    "address",
+    "article",
+    "aside",
+    "blockquote",
+    "br",
+    "dd",
+    "div",
+    "dl",
+    "dt",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "section",
+    "table",
+    "tr",
+    "ul",
+];
+
+/// 将文章HTML转换为按段落分行的纯文本，用于比较不同版本
+pub fn html_to_paragraphs(html: &str) -> Vec<String> {
+    let fragment = Html::parse_fragment(html);
+    let mut paragraphs = Vec::new();
+    let mut current = String::new();
+    collect_paragraphs(fragment.root_element(), &mut current, &mut paragraphs);
+    flush_paragraph(&mut current, &mut paragraphs);
+    paragraphs
+}
+
+/// 递归收集文字，遇到块级元素时分段
+fn collect_paragraphs(element: ElementRef, current: &mut String, paragraphs: &mut Vec<String>) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => current.push_str(&text.text),
+            Node::Element(_) => {
+                let Some(child) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                let is_block = BLOCK_TAGS.contains(&child.value().name());
+                if is_block {
+                    flush_paragraph(current, paragraphs);
+                }
+                collect_paragraphs(child, current, paragraphs);
+                if is_block {
+                    flush_paragraph(current, paragraphs);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// 合并空白后保存当前段落
+fn flush_paragraph(current: &mut String, paragraphs: &mut Vec<String>) {
+    let paragraph = current.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !paragraph.is_empty() {
+        paragraphs.push(paragraph);
+    }
+    current.clear();
+}
+
+/// 按行比较两个版本，基于最长公共子序列
+pub fn diff_lines(old: &[String], new: &[String]) -> Vec<DiffLine> {
+    // 去掉相同的开头和结尾，减少需要比较的行数
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_middle = &old[prefix..old.len() - suffix];
+    let new_middle = &new[prefix..new.len() - suffix];
+
+    // lcs[i][j]为old_middle[i..]和new_middle[j..]的最长公共子序列长度
+    let mut lcs = vec![vec![0u32; new_middle.len() + 1]; old_middle.len() + 1];
+    for i in (0..old_middle.len()).rev() {
+        for j in (0..new_middle.len()).rev() {
+            lcs[i][j] = if old_middle[i] == new_middle[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let line = |kind, text: &String| DiffLine {
+        kind,
+        text: text.clone(),
+    };
+    let mut result: Vec<DiffLine> = old[..prefix]
+        .iter()
+        .map(|text| line(DiffKind::Equal, text))
+        .collect();
+
+    let (mut i, mut j) = (0, 0);
+    while i < old_middle.len() && j < new_middle.len() {
+        if old_middle[i] == new_middle[j] {
+            result.push(line(DiffKind::Equal, &old_middle[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            result.push(line(DiffKind::Delete, &old_middle[i]));
+            i += 1;
+        } else {
+            result.push(line(DiffKind::Insert, &new_middle[j]));
+            j += 1;
+        }
+    }
+    result.extend(
+        old_middle[i..]
+            .iter()
+            .map(|text| line(DiffKind::Delete, text)),
+    );
+    result.extend(
+        new_middle[j..]
+            .iter()
+            .map(|text| line(DiffKind::Insert, text)),
+    );
+    result.extend(
+        old[old.len() - suffix..]
+            .iter()
+            .map(|text| line(DiffKind::Equal, text)),
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    fn diff(old: &[&str], new: &[&str]) -> Vec<(DiffKind, String)> {
+        diff_lines(&lines(old), &lines(new))
+            .into_iter()
+            .map(|line| (line.kind, line.text))
+            .collect()
+    }
+
+    #[test]
+    fn splits_html_into_paragraphs() {
+        assert_eq!(
+            html_to_paragraphs("<p>第一段 <b>加粗</b></p><p>第二段</p>"),
+            lines(&["第一段 加粗", "第二段"])
+        );
+        assert_eq!(
+            html_to_paragraphs("开头<div>中间<br>换行</div>结尾"),
+            lines(&["开头", "中间", "换行", "结尾"])
+        );
+        assert_eq!(
+            html_to_paragraphs("<ul><li>一</li><li>二</li></ul>"),
+            lines(&["一", "二"])
+        );
+    }
+
+    #[test]
+    fn collapses_whitespace_and_skips_empty_paragraphs() {
+        assert_eq!(
+            html_to_paragraphs(
+                "<p>  多个\n\t空白  </p><p> </p><p></p><span>行内</span><em>元素</em>"
+            ),
+            lines(&["多个 空白", "行内元素"])
+        );
+        assert!(html_to_paragraphs("").is_empty());
+    }
+
+    #[test]
+    fn diffs_identical_versions() {
+        assert_eq!(
+            diff(&["a", "b"], &["a", "b"]),
+            vec![
+                (DiffKind::Equal, "a".to_string()),
+                (DiffKind::Equal, "b".to_string())
+            ]
+        );
+        assert!(diff(&[], &[]).is_empty());
+    }
+
+    #[test]
+    fn diffs_insertions_and_deletions() {
+        assert_eq!(
+            diff(&["a", "b", "c"], &["a", "c", "d"]),
+            vec![
+                (DiffKind::Equal, "a".to_string()),
+                (DiffKind::Delete, "b".to_string()),
+                (DiffKind::Equal, "c".to_string()),
+                (DiffKind::Insert, "d".to_string()),
+            ]
+        );
+        assert_eq!(diff(&[], &["a"]), vec![(DiffKind::Insert, "a".to_string())]);
+        assert_eq!(diff(&["a"], &[]), vec![(DiffKind::Delete, "a".to_string())]);
+    }
+
+    #[test]
+    fn diffs_changed_line_as_delete_then_insert() {
+        assert_eq!(
+            diff(&["a", "old", "z"], &["a", "new", "z"]),
+            vec![
+                (DiffKind::Equal, "a".to_string()),
+                (DiffKind::Delete, "old".to_string()),
+                (DiffKind::Insert, "new".to_string()),
+                (DiffKind::Equal, "z".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_both_versions_in_order() {
+        let old = lines(&["x", "a", "b", "c", "y", "d"]);
+        let new = lines(&["a", "c", "e", "d", "f"]);
+        let result = diff_lines(&old, &new);
+        let version = |skip: DiffKind| {
+            result
+                .iter()
+                .filter(|line| line.kind != skip)
+                .map(|line| line.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(version(DiffKind::Insert), old);
+        assert_eq!(version(DiffKind::Delete), new);
+        // 最长公共子序列为a、c、d
+        assert_eq!(
+            result
+                .iter()
+                .filter(|line| line.kind == DiffKind::Equal)
+                .count(),
+            3
+        );
+    }
+}
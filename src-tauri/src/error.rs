@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+
+/// 应用错误类型，区分网络、HTTP、解析、数据库和AI接口等失败原因
+#[derive(Debug)]
+pub enum AppError {
+    /// DNS解析失败
+    Dns(String),
+    /// TLS握手或证书错误
+    Tls(String),
+    /// 无法建立连接
+    Connect(String),
+    /// 请求超时
+    Timeout(String),
+    /// 服务器返回错误状态码
+    Http { status: u16, url: String },
+    /// 服务器限流或暂时不可用（429/503）
+    RateLimited {
+        status: u16,
+        url: String,
+        /// 服务器通过Retry-After头指定的重试时间
+        retry_after: Option<DateTime<Utc>>,
+    },
+    /// 源内容或响应解析失败
+    Parse(String),
+    /// 数据库错误
+    Database(String),
+    /// AI接口错误
+    Ai(String),
+    /// 其他错误
+    Other(String),
+}
+
+impl AppError {
+    /// 错误类型名称，保存到数据库并返回给前端
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::Dns(_) => "dns",
+            AppError::Tls(_) => "tls",
+            AppError::Connect(_) => "connect",
+            AppError::Timeout(_) => "timeout",
+            AppError::Http { .. } => "http",
+            AppError::RateLimited { .. } => "rate_limited",
+            AppError::Parse(_) => "parse",
+            AppError::Database(_) => "database",
+            AppError::Ai(_) => "ai",
+            AppError::Other(_) => "other",
+        }
+    }
+
+    /// HTTP状态码（仅HTTP相关错误）
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            AppError::Http { status, .. } | AppError::RateLimited { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+
+    /// 服务器要求的重试时间
+    pub fn retry_after(&self) -> Option<DateTime<Utc>> {
+        match self {
+            AppError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for AppError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AppError::Dns(msg) => write!(f, "DNS解析失败: {}", msg),
+            AppError::Tls(msg) => write!(f, "TLS连接失败: {}", msg),
+            AppError::Connect(msg) => write!(f, "连接失败: {}", msg),
+            AppError::Timeout(msg) => write!(f, "请求超时: {}", msg),
+            AppError::Http { status, url } => {
+                write!(f, "获取RSS源失败: 服务器返回 {} ({})", status, url)
+            }
+            AppError::RateLimited {
+                status,
+                url,
+                retry_after: Some(retry_after),
+            } => write!(
+                f,
+                "获取RSS源失败: 服务器返回 {}，要求在 {} 之后重试 ({})",
+                status,
+                retry_after.to_rfc3339(),
+                url
+            ),
+            AppError::RateLimited { status, url, .. } => {
+                write!(f, "获取RSS源失败: 服务器返回 {} ({})", status, url)
+            }
+            AppError::Parse(msg) => write!(f, "解析失败: {}", msg),
+            AppError::Database(msg) => write!(f, "数据库错误: {}", msg),
+            AppError::Ai(msg) => write!(f, "AI接口错误: {}", msg),
+            AppError::Other(msg) => write!(f, "{}", msg),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+/// 以{ kind, status, message, retry_after }结构返回给前端
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("AppError", 4)?;
+        state.serialize_field("kind", self.kind())?;
+        state.serialize_field("status", &self.status())?;
+        state.serialize_field("message", &self.to_string())?;
+        state.serialize_field("retry_after", &self.retry_after())?;
+        state.end()
+    }
+}
+
+impl From<reqwest::Error> for AppError {
+    fn from(e: reqwest::Error) -> Self {
+        // reqwest只给出笼统的错误信息，具体原因需要从错误链中获取
+        let mut message = e.to_string();
+        let mut source = std::error::Error::source(&e);
+        while let Some(err) = source {
+            message.push_str(": ");
+            message.push_str(&err.to_string());
+            source = err.source();
+        }
+
+        if e.is_timeout() {
+            AppError::Timeout(message)
+        } else if let Some(status) = e.status() {
+            AppError::Http {
+                status: status.as_u16(),
+                url: e.url().map(|u| u.to_string()).unwrap_or_default(),
+            }
+        } else if e.is_connect() {
+            let lower = message.to_lowercase();
+            if lower.contains("dns") || lower.contains("lookup address") {
+                AppError::Dns(message)
+            } else if lower.contains("certificate") || lower.contains("tls") {
+                AppError::Tls(message)
+            } else {
+                AppError::Connect(message)
+            }
+        } else if e.is_decode() {
+            AppError::Parse(message)
+        } else {
+            AppError::Other(message)
+        }
+    }
+}
+
+impl From<rusqlite::Error> for AppError {
+    fn from(e: rusqlite::Error) -> Self {
+        AppError::Database(e.to_string())
+    }
+}
+
+impl From<serde_json::Error> for AppError {
+    fn from(e: serde_json::Error) -> Self {
+        AppError::Parse(e.to_string())
+    }
+}
+
+impl From<url::ParseError> for AppError {
+    fn from(e: url::ParseError) -> Self {
+        AppError::Other(format!("无效的URL: {}", e))
+    }
+}
+
+impl From<Box<dyn std::error::Error + Send + Sync>> for AppError {
+    fn from(e: Box<dyn std::error::Error + Send + Sync>) -> Self {
+        AppError::Other(e.to_string())
+    }
+}
+
+impl From<String> for AppError {
+    fn from(message: String) -> Self {
+        AppError::Other(message)
+    }
+}
+
+impl From<&str> for AppError {
+    fn from(message: &str) -> Self {
+        AppError::Other(message.to_string())
+    }
+}
@@ -0,0 +1,207 @@
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use scraper::{ElementRef, Html, Node, Selector};
+use std::collections::HashMap;
+
+/// 不属于正文的元素，提取时整体丢弃
+const REMOVED_TAGS: &[&str] = &[
+    "script", "style", "noscript", "iframe", "form", "nav", "aside", "footer", "header", "button",
+    "input", "select", "textarea", "svg", "canvas", "object", "embed",
+];
+
+/// 没有结束标签的元素
+const VOID_TAGS: &[&str] = &["area", "br", "col", "hr", "img", "source", "track", "wbr"];
+
+/// class/id中表示正文的关键字
+const POSITIVE_HINTS: &[&str] = &[
+    "article", "content", "entry", "post", "main", "body", "text", "story", "blog",
+];
+
+/// class/id中表示非正文的关键字
+const NEGATIVE_HINTS: &[&str] = &[
+    "comment",
+    "footer",
+    "sidebar",
+    "nav",
+    "menu",
+    "share",
+    "social",
+    "related",
+    "promo",
+    "advert",
+    "sponsor",
+    "widget",
+    "popup",
+    "subscribe",
+    "newsletter",
+    "cookie",
+    "breadcrumb",
+];
+
+/// 正文的最少字符数，少于此值视为提取失败
+const MIN_CONTENT_LENGTH: usize = 200;
+
+/// 从网页中提取正文HTML（类似Readability的算法）
+///
+/// 以段落文字为依据给父元素和祖父元素打分，结合class/id关键字和链接密度选出得分最高的元素，
+/// 并去掉其中的脚本、导航、评论等非正文内容。未找到足够长的正文时返回None
+pub fn extract_main_content(html: &str) -> Option<String> {
+    let document = Html::parse_document(html);
+    let paragraph_selector = Selector::parse("p, pre, blockquote, td").ok()?;
+
+    // 给包含段落的元素打分
+    let mut scores = HashMap::new();
+    for paragraph in document.select(&paragraph_selector) {
+        let text = paragraph.text().collect::<String>();
+        let text_length = text.trim().chars().count();
+        if text_length < 25 {
+            continue;
+        }
+
+        // 逗号越多、文字越长，越可能是正文
+        let commas = text.matches([',', '，', '、']).count();
+        let score = 1.0 + commas as f64 + (text_length as f64 / 100.0).min(3.0);
+
+        let Some(parent) = paragraph.parent().and_then(ElementRef::wrap) else {
+            continue;
+        };
+        *scores
+            .entry(parent.id())
+            .or_insert_with(|| initial_score(parent)) += score;
+        if let Some(grandparent) = parent.parent().and_then(ElementRef::wrap) {
+            *scores
+                .entry(grandparent.id())
+                .or_insert_with(|| initial_score(grandparent)) += score / 2.0;
+        }
+    }
+
+    // 按链接密度修正得分后选出最佳元素
+    let best = scores
+        .into_iter()
+        .filter_map(|(id, score)| {
+            let element = ElementRef::wrap(document.tree.get(id)?)?;
+            Some((element, score * (1.0 - link_density(element))))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(element, _)| element)?;
+
+    let mut content = String::new();
+    serialize_children(best, &mut content);
+    let text_length = Html::parse_fragment(&content)
+        .root_element()
+        .text()
+        .map(|text| text.trim().chars().count())
+        .sum::<usize>();
+    if text_length < MIN_CONTENT_LENGTH {
+        return None;
+    }
+
+    Some(format!("<div>{}</div>", content.trim()))
+}
+
+/// 根据标签名和class/id计算元素的初始得分
+fn initial_score(element: ElementRef) -> f64 {
+    let tag_score = match element.value().name() {
+        "article" => 10.0,
+        "div" | "main" | "section" => 5.0,
+        "pre" | "td" | "blockquote" => 3.0,
+        "form" | "ul" | "ol" | "dl" | "li" => -3.0,
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "th" => -5.0,
+        _ => 0.0,
+    };
+    tag_score + class_weight(element)
+}
+
+/// 根据class和id中的关键字计算权重
+fn class_weight(element: ElementRef) -> f64 {
+    let names = format!(
+        "{} {}",
+        element.value().attr("class").unwrap_or(""),
+        element.value().attr("id").unwrap_or("")
+    )
+    .to_lowercase();
+
+    let mut weight = 0.0;
+    if POSITIVE_HINTS.iter().any(|hint| names.contains(hint)) {
+        weight += 25.0;
+    }
+    if NEGATIVE_HINTS.iter().any(|hint| names.contains(hint)) {
+        weight -= 25.0;
+    }
+    weight
+}
+
+/// 链接文字占全部文字的比例
+fn link_density(element: ElementRef) -> f64 {
+    let text_length = element
+        .text()
+        .map(|text| text.chars().count())
+        .sum::<usize>();
+    if text_length == 0 {
+        return 0.0;
+    }
+    let link_selector = Selector::parse("a").unwrap();
+    let link_length = element
+        .select(&link_selector)
+        .flat_map(|link| link.text())
+        .map(|text| text.chars().count())
+        .sum::<usize>();
+    link_length as f64 / text_length as f64
+}
+
+/// 是否在提取结果中丢弃该元素
+fn is_removed(element: ElementRef) -> bool {
+    if REMOVED_TAGS.contains(&element.value().name()) {
+        return true;
+    }
+    // 带有非正文关键字的较短区块（评论、分享按钮、相关文章等）
+    class_weight(element) < 0.0
+        && element
+            .text()
+            .map(|text| text.chars().count())
+            .sum::<usize>()
+            < 500
+}
+
+/// 将元素的子节点序列化为HTML，去掉非正文元素以及事件、样式属性
+fn serialize_children(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(&encode_text(&text.text)),
+            Node::Element(_) => {
+                let Some(child) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                if is_removed(child) {
+                    continue;
+                }
+
+                let name = child.value().name();
+                if name == "img" {
+                    // 图片保持原样，便于之后修复图片地址
+                    out.push_str(&child.html());
+                    continue;
+                }
+
+                out.push('<');
+                out.push_str(name);
+                for (attr, value) in child.value().attrs() {
+                    if attr.starts_with("on") || matches!(attr, "style" | "class" | "id") {
+                        continue;
+                    }
+                    out.push_str(&format!(
+                        " {}=\"{}\"",
+                        attr,
+                        encode_double_quoted_attribute(value)
+                    ));
+                }
+                out.push('>');
+                if VOID_TAGS.contains(&name) {
+                    continue;
+                }
+                serialize_children(child, out);
+                out.push_str(&format!("</{}>", name));
+            }
+            _ => {}
+        }
+    }
+}
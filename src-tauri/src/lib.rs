@@ -1,8 +1,10 @@
+use async_trait::async_trait;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::fs::{File, read_dir};
 use std::io::Read;
+use std::sync::Arc;
 use std::time::SystemTime;
 use tauri::{Emitter, Manager, State, async_runtime::Mutex};
 use tauri_plugin_notification::NotificationExt;
@@ -12,14 +14,33 @@ use uuid::Uuid;
 
 // 导入自定义模块
 mod ai_translator;
+mod canonical;
+mod dates;
 mod db;
+mod diff;
+mod error;
+mod extractor;
+mod local;
 mod models;
+mod network;
 mod rss;
+mod sanitizer;
+mod websub;
 
 use crate::ai_translator::AI_TRANSLATOR;
+use crate::canonical::LinkRules;
 use crate::db::DbManager;
-use crate::models::{AIPlatform, Article, Feed, FeedGroup};
-use crate::rss::RssUpdater;
+use crate::error::AppError;
+use crate::local::{ExecCommand, ExecFetcher};
+use crate::models::{
+    AIPlatform, Article, ArticleDiff, ArticleRevision, Feed, FeedCandidate, FeedGroup, FeedHealth,
+    FeedPreview, FeedRequestOptions, WebSubSubscription,
+};
+use crate::network::NetworkOptions;
+use crate::rss::{
+    BackfillOptions, BrowserOptions, FeedDiscoverer, FeedUpdate, RssUpdater, UpdateLimits,
+};
+use crate::websub::{CallbackHandler, IntentVerification};
 
 /// 配置文件结构
 #[derive(Debug, Deserialize, Serialize, Default, Clone)]
@@ -28,6 +49,175 @@ struct Config {
     db: Option<DbConfig>,
     /// 更新配置
     update: Option<UpdateConfig>,
+    /// 浏览器回退配置
+    browser: Option<BrowserConfig>,
+    /// 网络配置（代理和CA证书）
+    network: Option<NetworkConfig>,
+    /// 文章链接规范化配置
+    links: Option<LinksConfig>,
+    /// 回溯历史文章配置
+    backfill: Option<BackfillConfig>,
+    /// WebSub推送订阅配置
+    websub: Option<WebSubConfig>,
+    /// 本地命令源配置
+    exec: Option<ExecConfig>,
+}
+
+/// 浏览器回退配置（用于获取会拦截普通请求的RSS源）
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+struct BrowserConfig {
+    /// Chrome/Chromium/Edge可执行文件路径，未设置时自动查找
+    path: Option<String>,
+    /// 使用无头模式（默认false，在屏幕外显示窗口以避免被网站拦截）
+    headless: Option<bool>,
+}
+
+impl BrowserConfig {
+    /// 转换为RSS更新器使用的浏览器选项
+    fn options(&self) -> BrowserOptions {
+        BrowserOptions {
+            path: self.path.as_ref().map(std::path::PathBuf::from),
+            headless: self.headless.unwrap_or(false),
+        }
+    }
+}
+
+/// 读取配置中的浏览器回退选项
+fn browser_options(config: &Config) -> BrowserOptions {
+    config
+        .browser
+        .as_ref()
+        .map(|browser_config| browser_config.options())
+        .unwrap_or_default()
+}
+
+/// 网络配置，同时用于RSS获取和AI接口
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+struct NetworkConfig {
+    /// 代理地址，支持http://、https://和socks5://
+    proxy: Option<String>,
+    /// 不使用代理的主机列表，逗号分隔
+    no_proxy: Option<String>,
+    /// 额外信任的CA证书文件路径（PEM或DER格式）
+    ca_certs: Option<Vec<String>>,
+}
+
+impl NetworkConfig {
+    /// 转换为HTTP客户端使用的网络选项
+    fn options(&self) -> NetworkOptions {
+        NetworkOptions {
+            proxy: self.proxy.clone(),
+            no_proxy: self.no_proxy.clone(),
+            ca_certs: self
+                .ca_certs
+                .iter()
+                .flatten()
+                .map(std::path::PathBuf::from)
+                .collect(),
+        }
+    }
+}
+
+/// 读取配置中的网络选项
+fn network_options(config: &Config) -> NetworkOptions {
+    config
+        .network
+        .as_ref()
+        .map(|network_config| network_config.options())
+        .unwrap_or_default()
+}
+
+/// 文章链接规范化配置，规则格式见config.toml中的说明
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+struct LinksConfig {
+    /// 使用内置的跟踪参数规则（utm_*、fbclid等，默认true）
+    default_rules: Option<bool>,
+    /// 额外去除的参数
+    strip_params: Option<Vec<String>>,
+    /// 即使匹配规则也保留的参数
+    keep_params: Option<Vec<String>>,
+}
+
+impl LinksConfig {
+    /// 转换为链接规范化规则
+    fn rules(&self) -> LinkRules {
+        LinkRules::new(
+            self.default_rules.unwrap_or(true),
+            self.strip_params.as_deref().unwrap_or_default(),
+            self.keep_params.as_deref().unwrap_or_default(),
+        )
+    }
+}
+
+/// 读取配置中的链接规范化规则
+fn link_rules(config: &Config) -> LinkRules {
+    config
+        .links
+        .as_ref()
+        .map(|links_config| links_config.rules())
+        .unwrap_or_default()
+}
+
+/// 本地命令源配置
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+struct ExecConfig {
+    /// 命令运行时间上限（秒，默认60）
+    timeout: Option<u64>,
+    /// 可以运行的命令，RSS源地址为exec:{名称}
+    commands: Option<std::collections::HashMap<String, ExecCommandConfig>>,
+}
+
+/// 本地命令源的命令配置
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+struct ExecCommandConfig {
+    /// 可执行文件
+    command: String,
+    /// 命令行参数
+    args: Option<Vec<String>>,
+    /// 工作目录
+    dir: Option<String>,
+}
+
+impl ExecConfig {
+    /// 转换为本地命令源获取器
+    fn fetcher(&self) -> ExecFetcher {
+        let commands = self
+            .commands
+            .iter()
+            .flatten()
+            .map(|(name, command_config)| {
+                let command = ExecCommand {
+                    program: command_config.command.clone(),
+                    args: command_config.args.clone().unwrap_or_default(),
+                    dir: command_config.dir.as_ref().map(std::path::PathBuf::from),
+                };
+                (name.clone(), command)
+            })
+            .collect();
+        let timeout = self
+            .timeout
+            .map(tokio::time::Duration::from_secs)
+            .unwrap_or(ExecFetcher::DEFAULT_TIMEOUT);
+        ExecFetcher::new(commands, timeout)
+    }
+}
+
+/// 读取配置中的本地命令源设置
+fn exec_fetcher(config: &Config) -> ExecFetcher {
+    config
+        .exec
+        .as_ref()
+        .map(|exec_config| exec_config.fetcher())
+        .unwrap_or_default()
+}
+
+/// 按配置文件中的浏览器、网络、链接规范化和本地命令设置创建RSS更新器
+fn configured_rss_updater(config: &Config) -> RssUpdater {
+    RssUpdater::new()
+        .with_browser(browser_options(config))
+        .with_network(network_options(config))
+        .with_link_rules(link_rules(config))
+        .with_exec(exec_fetcher(config))
 }
 
 /// 数据库配置
@@ -47,6 +237,171 @@ struct DbConfig {
 struct UpdateConfig {
     /// 自动更新间隔（秒）
     interval: Option<u64>,
+    /// 同时更新的RSS源数量上限（默认8）
+    max_concurrency: Option<usize>,
+    /// 同一主机同时进行的请求数量上限（默认2）
+    per_host_concurrency: Option<usize>,
+    /// 同一主机两次请求之间的最小间隔（毫秒，默认500）
+    per_host_delay_ms: Option<u64>,
+    /// 根据各RSS源的发文频率自动计算更新间隔（默认关闭）
+    adaptive: Option<bool>,
+}
+
+impl UpdateConfig {
+    /// 转换为RSS更新器使用的并发限制，未配置的项使用默认值
+    fn limits(&self) -> UpdateLimits {
+        let defaults = UpdateLimits::default();
+        UpdateLimits {
+            max_concurrency: self.max_concurrency.unwrap_or(defaults.max_concurrency),
+            per_host_concurrency: self
+                .per_host_concurrency
+                .unwrap_or(defaults.per_host_concurrency),
+            per_host_delay: self
+                .per_host_delay_ms
+                .map(std::time::Duration::from_millis)
+                .unwrap_or(defaults.per_host_delay),
+        }
+    }
+}
+
+/// 回溯历史文章配置
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+struct BackfillConfig {
+    /// 最多获取的页数（默认50）
+    max_pages: Option<usize>,
+    /// 最多获取的文章数（默认1000）
+    max_articles: Option<usize>,
+}
+
+impl BackfillConfig {
+    /// 转换为RSS更新器使用的回溯限制，未配置的项使用默认值
+    fn options(&self) -> BackfillOptions {
+        let defaults = BackfillOptions::default();
+        BackfillOptions {
+            max_pages: self.max_pages.unwrap_or(defaults.max_pages),
+            max_articles: self.max_articles.unwrap_or(defaults.max_articles),
+        }
+    }
+}
+
+/// 读取配置中的回溯限制
+fn backfill_options(config: &Config) -> BackfillOptions {
+    config
+        .backfill
+        .as_ref()
+        .map(|backfill_config| backfill_config.options())
+        .unwrap_or_default()
+}
+
+/// WebSub回调服务器的默认监听地址
+const DEFAULT_WEBSUB_LISTEN: &str = "127.0.0.1:8849";
+/// WebSub订阅维护任务的检查间隔（秒）
+const WEBSUB_CHECK_INTERVAL: u64 = 10 * 60;
+/// 订阅请求发出后等待推送中心确认的时间（秒），超时后重新发送
+const WEBSUB_PENDING_TIMEOUT: i64 = 60 * 60;
+/// 订阅到期前多久续订（秒）
+const WEBSUB_RENEW_BEFORE: i64 = 6 * 60 * 60;
+/// 推送中心给出的订阅有效期上限（秒），超过时按上限记录，到期前续订
+const WEBSUB_MAX_LEASE: i64 = 365 * 24 * 60 * 60;
+
+/// WebSub推送订阅配置
+#[derive(Debug, Deserialize, Serialize, Default, Clone)]
+struct WebSubConfig {
+    /// 启用推送订阅（默认false）
+    enabled: Option<bool>,
+    /// 回调服务器监听地址（默认127.0.0.1:8849）
+    listen: Option<String>,
+    /// 推送中心访问回调服务器使用的地址，默认为http://{listen}
+    callback_url: Option<String>,
+    /// 请求的订阅有效期（秒，默认7天），推送中心可以调整
+    lease_seconds: Option<u64>,
+    /// 订阅生效的源的后备轮询间隔（秒，默认3600）
+    fallback_interval: Option<i64>,
+}
+
+impl WebSubConfig {
+    /// 回调服务器监听地址
+    fn listen(&self) -> String {
+        self.listen
+            .clone()
+            .unwrap_or_else(|| DEFAULT_WEBSUB_LISTEN.to_string())
+    }
+
+    /// 回调地址的前缀
+    fn callback_base(&self) -> String {
+        self.callback_url
+            .clone()
+            .unwrap_or_else(|| format!("http://{}", self.listen()))
+    }
+
+    /// 请求的订阅有效期（秒）
+    fn lease_seconds(&self) -> u64 {
+        self.lease_seconds.unwrap_or(7 * 24 * 60 * 60)
+    }
+
+    /// 订阅生效的源的后备轮询间隔（秒）
+    fn fallback_interval(&self) -> i64 {
+        self.fallback_interval.unwrap_or(60 * 60)
+    }
+}
+
+/// 读取配置中的WebSub设置，未启用时返回None
+fn websub_config(config: &Config) -> Option<&WebSubConfig> {
+    config
+        .websub
+        .as_ref()
+        .filter(|websub_config| websub_config.enabled.unwrap_or(false))
+}
+
+/// 持续失败超过该天数的RSS源视为失效（默认值）
+const DEFAULT_DEAD_AFTER_DAYS: i64 = 30;
+
+/// 自适应更新间隔的下限（秒）
+const ADAPTIVE_MIN_INTERVAL: i64 = 5 * 60;
+/// 自适应更新间隔的上限（秒）
+const ADAPTIVE_MAX_INTERVAL: i64 = 6 * 60 * 60;
+/// 自适应模式统计发文频率的时间窗口（天）
+const ADAPTIVE_WINDOW_DAYS: i64 = 30;
+
+/// 根据平均发文间隔计算自适应更新间隔，平均每两篇文章之间拉取约24次
+///
+/// 例如每天发文的博客每小时拉取一次，频繁发文的新闻源保持5分钟
+fn adaptive_update_interval(posting_interval: Option<i64>) -> i64 {
+    posting_interval
+        .map(|interval| interval / 24)
+        .unwrap_or(ADAPTIVE_MAX_INTERVAL)
+        .clamp(ADAPTIVE_MIN_INTERVAL, ADAPTIVE_MAX_INTERVAL)
+}
+
+/// 两次自动更新检查之间的最短等待时间（秒），也是判断源是否到期时预留的余量
+const AUTO_UPDATE_MIN_DELAY: i64 = 30;
+
+/// RSS源下次需要更新的时间，interval为该源的更新间隔（秒）
+///
+/// 同时考虑失败重试和调度提示（Retry-After、TTL）的时间，从未更新过的源返回None
+fn feed_due_time(feed: &Feed, interval: i64) -> Option<chrono::DateTime<Utc>> {
+    let due = feed.last_updated.and_then(|last_updated| {
+        last_updated.checked_add_signed(chrono::Duration::seconds(interval))
+    });
+    due.max(feed.next_retry_time)
+}
+
+/// 计算距下次自动更新检查的等待时间（秒）
+///
+/// 取各源下次需要更新的时间中最早的一个，不超过全局更新间隔，
+/// 使短于全局间隔的源更新间隔和自适应间隔能够按时生效。更新期间到期的源在最短等待时间后更新
+fn next_auto_update_delay(
+    due_times: impl IntoIterator<Item = chrono::DateTime<Utc>>,
+    now: chrono::DateTime<Utc>,
+    update_interval: u64,
+) -> u64 {
+    due_times
+        .into_iter()
+        .min()
+        .map(|due| {
+            ((due - now).num_seconds().max(AUTO_UPDATE_MIN_DELAY) as u64).min(update_interval)
+        })
+        .unwrap_or(update_interval)
 }
 
 // 配置缓存结构体
@@ -184,13 +539,13 @@ async fn update_single_feed(
     app: tauri::AppHandle,
     app_state: State<'_, AppState>,
     feed_id: i64,
-) -> Result<(), String> {
+) -> Result<(), AppError> {
     // 获取指定RSS源
     let mut feed = {
         let mut db_manager = app_state.db_manager.lock().await;
         db_manager.get_feed_by_id(feed_id).map_err(|e| {
             eprintln!("Failed to get feed: {}", e);
-            format!("Failed to get feed: {}", e)
+            AppError::from(e)
         })?
     };
 
@@ -198,20 +553,43 @@ async fn update_single_feed(
     {
         let mut db_manager = app_state.db_manager.lock().await;
         // 重置失败尝试次数和下次重试时间
-        if let Err(e) = db_manager.update_feed_success(feed.id, Utc::now()) {
+        if let Err(e) = db_manager.reset_feed_retry(feed.id) {
             eprintln!("Failed to reset feed failure status: {}", e);
         }
         // 更新内存中的feed对象，确保使用最新状态
         feed = db_manager.get_feed_by_id(feed_id).map_err(|e| {
             eprintln!("Failed to get updated feed: {}", e);
-            format!("Failed to get updated feed: {}", e)
+            AppError::from(e)
         })?;
     }
 
     // 更新RSS源
-    let rss_updater = RssUpdater::new();
+    let rss_updater = configured_rss_updater(&read_config_file());
     match rss_updater.update_feed(&feed).await {
-        Ok(articles) => {
+        Ok(mut update) => {
+            // 源已永久迁移时更新地址
+            feed = apply_permanent_redirect(&app_state, &feed, &mut update).await;
+            save_websub_hub(&app_state, &feed, &update).await;
+            fetch_full_content(&app_state, &rss_updater, &feed, &mut update).await;
+
+            // 更新成功状态，并保存缓存验证头供下次条件请求使用
+            {
+                let mut db_manager = app_state.db_manager.lock().await;
+                if let Err(e) =
+                    db_manager.update_feed_success(feed.id, Utc::now(), update.next_update_time)
+                {
+                    eprintln!("Failed to update feed success status: {}", e);
+                }
+                if let Err(e) = db_manager.update_feed_cache_headers(
+                    feed.id,
+                    update.etag.as_deref(),
+                    update.last_modified.as_deref(),
+                ) {
+                    eprintln!("Failed to update feed cache headers: {}", e);
+                }
+            }
+            let articles = update.articles;
+
             // 检查哪些文章需要翻译
             let mut articles_to_translate = Vec::new();
             let mut articles_to_save = Vec::new();
@@ -223,7 +601,7 @@ async fn update_single_feed(
                 for article in articles {
                     if feed.translate_enabled {
                         if let Ok(needs_translation) =
-                            db_manager.article_needs_translation(article.feed_id, &article.link)
+                            db_manager.article_needs_translation(&article)
                         {
                             if needs_translation {
                                 articles_to_translate.push(article.clone());
@@ -336,35 +714,150 @@ async fn update_single_feed(
             // 更新失败状态
             {
                 let mut db_manager = app_state.db_manager.lock().await;
-                if let Err(err) = db_manager.update_feed_failure(feed.id, &e.to_string()) {
+                if let Err(err) = db_manager.update_feed_failure(feed.id, &e) {
                     eprintln!("Failed to update feed failure status: {}", err);
                 }
             }
 
             // 即使更新失败，也通知前端，以便清除加载状态
-            if let Err(e) = app.emit("feed_updated", Some(feed.id)) {
-                eprintln!("Failed to emit feed_updated event: {}", e);
+            if let Err(emit_err) = app.emit("feed_updated", Some(feed.id)) {
+                eprintln!("Failed to emit feed_updated event: {}", emit_err);
             }
+
+            // 将结构化的错误返回给前端
+            return Err(e);
         }
     }
 
     Ok(())
 }
 
+/// 处理RSS源的永久重定向：更新数据库中的源地址，并将文章关联到处理后的源
+///
+/// 返回处理后的源（新地址已被订阅时为合并后的目标源）
+async fn apply_permanent_redirect(
+    app_state: &AppState,
+    feed: &Feed,
+    update: &mut FeedUpdate,
+) -> Feed {
+    let Some(new_url) = update.permanent_redirect.clone() else {
+        return feed.clone();
+    };
+
+    let mut db_manager = app_state.db_manager.lock().await;
+    let target_id = match db_manager.apply_feed_redirect(feed.id, &new_url) {
+        Ok(target_id) => target_id,
+        Err(e) => {
+            eprintln!(
+                "Failed to apply permanent redirect for {}: {}",
+                feed.name, e
+            );
+            return feed.clone();
+        }
+    };
+    println!("RSS源 {} 已永久迁移到: {}", feed.name, new_url);
+
+    let new_feed = db_manager
+        .get_feed_by_id(target_id)
+        .unwrap_or_else(|_| Feed {
+            id: target_id,
+            url: new_url,
+            ..feed.clone()
+        });
+    for article in &mut update.articles {
+        article.feed_id = new_feed.id;
+    }
+    new_feed
+}
+
+/// 保存更新时发现的WebSub推送中心，由订阅维护任务发送订阅请求
+async fn save_websub_hub(app_state: &AppState, feed: &Feed, update: &FeedUpdate) {
+    let Some(websub) = &update.websub else {
+        return;
+    };
+    let db_manager = app_state.db_manager.lock().await;
+    if let Err(e) = db_manager.save_websub_hub(feed.id, &websub.hub, &websub.topic) {
+        eprintln!("Failed to save WebSub hub for {}: {}", feed.name, e);
+    }
+}
+
+/// 为开启了全文抓取的RSS源下载新文章的正文，已保存过的文章不再重复下载
+async fn fetch_full_content(
+    app_state: &AppState,
+    rss_updater: &RssUpdater,
+    feed: &Feed,
+    update: &mut FeedUpdate,
+) {
+    if !feed.fetch_full_content || update.articles.is_empty() {
+        return;
+    }
+
+    // 新文章和被发布者修改过的文章需要获取全文
+    let new_articles = {
+        let db_manager = app_state.db_manager.lock().await;
+        update
+            .articles
+            .iter_mut()
+            .filter(|article| db_manager.article_needs_update(article).unwrap_or(true))
+            .collect::<Vec<_>>()
+    };
+    if new_articles.is_empty() {
+        return;
+    }
+
+    println!(
+        "开始获取 {} 的 {} 篇新文章全文",
+        feed.name,
+        new_articles.len()
+    );
+    rss_updater.fetch_full_content(feed, new_articles).await;
+}
+
+// 异步Tauri命令：预览RSS源（不写入数据库）
+#[tauri::command(async)]
+async fn preview_feed(
+    url: String,
+    request_options: Option<FeedRequestOptions>,
+) -> Result<FeedPreview, AppError> {
+    let rss_updater = configured_rss_updater(&read_config_file());
+    let request_options = request_options.unwrap_or_default();
+    rss_updater
+        .preview_feed(&url, &request_options)
+        .await
+        .map_err(|e| {
+            eprintln!("Failed to preview feed {}: {}", url, e);
+            e
+        })
+}
+
 // 异步Tauri命令：添加RSS源
 #[tauri::command(async)]
 async fn add_feed(
     app: tauri::AppHandle,
     app_state: State<'_, AppState>,
     feed: Feed,
-) -> Result<i64, String> {
+    validate: Option<bool>,
+    backfill: Option<bool>,
+) -> Result<i64, AppError> {
+    // 默认先获取并解析RSS源，失败则拒绝添加
+    if validate.unwrap_or(true) {
+        let rss_updater = configured_rss_updater(&read_config_file());
+        rss_updater
+            .preview_feed(&feed.url, &feed.request_options)
+            .await
+            .map_err(|e| {
+                eprintln!("Feed validation failed for {}: {}", feed.url, e);
+                e
+            })?;
+    }
+
     // 将RSS源添加到数据库
     let feed_id = {
         let mut db_manager = app_state.db_manager.lock().await;
 
         db_manager.add_feed(&feed).map_err(|e| {
             eprintln!("Failed to add feed to database: {}", e);
-            format!("Failed to add feed: {}", e)
+            AppError::from(e)
         })?
     }; // 作用域结束，db_manager锁释放
 
@@ -381,16 +874,34 @@ async fn add_feed(
         tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
 
         // 更新RSS源
-        let rss_updater = RssUpdater::new();
+        let rss_updater = configured_rss_updater(&read_config_file());
         let result = rss_updater.update_feed(&new_feed).await;
 
         // 处理更新结果
-        if let Ok(articles) = result {
+        if let Ok(mut update) = result {
             // 获取应用实例和状态
             if let Some(window) = app_clone.get_webview_window("main") {
                 let app_handle = window.app_handle();
                 let app_state = app_handle.state::<AppState>();
 
+                // 源已永久迁移时更新地址
+                let new_feed = apply_permanent_redirect(&app_state, &new_feed, &mut update).await;
+                save_websub_hub(&app_state, &new_feed, &update).await;
+                fetch_full_content(&app_state, &rss_updater, &new_feed, &mut update).await;
+
+                // 保存缓存验证头，供下次条件请求使用
+                {
+                    let mut db_manager = app_state.db_manager.lock().await;
+                    if let Err(e) = db_manager.update_feed_cache_headers(
+                        new_feed.id,
+                        update.etag.as_deref(),
+                        update.last_modified.as_deref(),
+                    ) {
+                        eprintln!("Failed to update feed cache headers: {}", e);
+                    }
+                }
+                let articles = update.articles;
+
                 // 检查哪些文章需要翻译
                 let mut articles_to_translate = Vec::new();
                 let mut articles_to_save = Vec::new();
@@ -404,7 +915,7 @@ async fn add_feed(
                         if new_feed.translate_enabled {
                             // 检查文章是否需要翻译
                             let needs_translation = db_manager
-                                .article_needs_translation(article.feed_id, &article.link)
+                                .article_needs_translation(&article)
                                 .unwrap_or(true);
 
                             if needs_translation {
@@ -543,11 +1054,87 @@ async fn add_feed(
                 eprintln!("Failed to emit feed_updated event: {}", e);
             }
         }
+
+        // 首次更新完成后回溯历史文章
+        if backfill.unwrap_or(false) {
+            let app_state = app_clone.state::<AppState>();
+            if let Err(e) = backfill_feed_articles(&app_clone, &app_state, &new_feed).await {
+                eprintln!("Failed to backfill feed {}: {}", new_feed.name, e);
+            }
+        }
     });
 
     Ok(feed_id)
 }
 
+/// 回溯RSS源的历史文章并保存，进度通过backfill_progress事件发送给前端
+///
+/// 回溯的文章数量较多，不进行AI翻译，也不发送新文章通知，返回新增的文章数
+async fn backfill_feed_articles(
+    app: &tauri::AppHandle,
+    app_state: &AppState,
+    feed: &Feed,
+) -> Result<usize, AppError> {
+    let config = read_config_file();
+    let rss_updater = configured_rss_updater(&config);
+    let articles = rss_updater
+        .backfill_feed(feed, &backfill_options(&config), |progress| {
+            if let Err(e) = app.emit("backfill_progress", progress) {
+                eprintln!("Failed to emit backfill_progress event: {}", e);
+            }
+        })
+        .await?;
+
+    let mut new_article_count = 0;
+    for article in &articles {
+        let db_manager = app_state.db_manager.lock().await;
+        match db_manager.add_article(article) {
+            Ok(true) => new_article_count += 1,
+            Ok(false) => {}
+            Err(e) => eprintln!("Failed to save backfilled article {}: {}", article.link, e),
+        }
+    }
+    println!("回溯完成: {} 新增 {} 篇文章", feed.name, new_article_count);
+
+    if let Err(e) = app.emit("feed_updated", Some(feed.id)) {
+        eprintln!("Failed to emit feed_updated event: {}", e);
+    }
+    Ok(new_article_count)
+}
+
+// Tauri命令：回溯已订阅RSS源的历史文章，进度通过backfill_progress事件发送
+#[tauri::command(async, rename_all = "camelCase")]
+async fn backfill_feed(
+    app: tauri::AppHandle,
+    app_state: State<'_, AppState>,
+    feed_id: i64,
+) -> Result<usize, AppError> {
+    let feed = {
+        let db_manager = app_state.db_manager.lock().await;
+        db_manager.get_feed_by_id(feed_id).map_err(|e| {
+            eprintln!("Failed to get feed from database: {}", e);
+            AppError::from(e)
+        })?
+    };
+
+    backfill_feed_articles(&app, &app_state, &feed)
+        .await
+        .map_err(|e| {
+            eprintln!("Failed to backfill feed {}: {}", feed.name, e);
+            e
+        })
+}
+
+// Tauri命令：根据网站地址自动发现RSS源
+#[tauri::command(async)]
+async fn discover_feeds(url: String) -> Result<Vec<FeedCandidate>, AppError> {
+    let discoverer = FeedDiscoverer::new().with_network(network_options(&read_config_file()));
+    discoverer.discover(&url).await.map_err(|e| {
+        eprintln!("Failed to discover feeds for {}: {}", url, e);
+        e
+    })
+}
+
 // Tauri命令：获取所有RSS源
 #[tauri::command(async)]
 async fn get_all_feeds(app_state: State<'_, AppState>) -> Result<Vec<Feed>, String> {
@@ -626,10 +1213,41 @@ async fn delete_feed(app_state: State<'_, AppState>, feed_id: i64) -> Result<(),
     })
 }
 
+// Tauri命令：获取所有RSS源的健康报告
+#[tauri::command(async, rename_all = "camelCase")]
+async fn get_feed_health(
+    app_state: State<'_, AppState>,
+    dead_after_days: Option<i64>,
+) -> Result<Vec<FeedHealth>, String> {
+    let db_manager = app_state.db_manager.lock().await;
+    db_manager
+        .get_feed_health(dead_after_days.unwrap_or(DEFAULT_DEAD_AFTER_DAYS))
+        .map_err(|e| {
+            eprintln!("Failed to get feed health from database: {}", e);
+            format!("Failed to get feed health: {}", e)
+        })
+}
+
+// Tauri命令：批量删除RSS源（用于清理失效的订阅）
+#[tauri::command(async, rename_all = "camelCase")]
+async fn delete_feeds(app_state: State<'_, AppState>, feed_ids: Vec<i64>) -> Result<(), String> {
+    let mut db_manager = app_state.db_manager.lock().await;
+    db_manager.delete_feeds(&feed_ids).map_err(|e| {
+        eprintln!("Failed to delete feeds from database: {}", e);
+        format!("Failed to delete feeds: {}", e)
+    })
+}
+
 // Tauri命令：更新RSS源
 #[tauri::command(async)]
-async fn update_feed(app_state: State<'_, AppState>, feed: Feed) -> Result<(), String> {
+async fn update_feed(app_state: State<'_, AppState>, mut feed: Feed) -> Result<(), String> {
     let mut db_manager = app_state.db_manager.lock().await;
+    // 前端只能看到密钥的占位符，未修改的密钥使用已保存的值
+    let stored = db_manager.get_feed_by_id(feed.id).map_err(|e| {
+        eprintln!("Failed to get feed {} from database: {}", feed.id, e);
+        format!("Failed to update feed: {}", e)
+    })?;
+    feed.request_options.restore_secrets(&stored.request_options);
     db_manager.update_feed(&feed).map_err(|e| {
         eprintln!("Failed to update feed in database: {}", e);
         format!("Failed to update feed: {}", e)
@@ -689,6 +1307,23 @@ async fn get_feeds_by_group(
     })
 }
 
+// Tauri命令：获取RSS源中的播客节目（带有音频、视频附件的文章）
+#[tauri::command(async, rename_all = "camelCase")]
+async fn get_episodes_by_feed(
+    app_state: State<'_, AppState>,
+    feed_id: i64,
+    limit: u32,
+    offset: u32,
+) -> Result<Vec<Article>, String> {
+    let db_manager = app_state.db_manager.lock().await;
+    db_manager
+        .get_episodes_by_feed(feed_id, limit, offset)
+        .map_err(|e| {
+            eprintln!("Failed to get episodes by feed from database: {}", e);
+            format!("Failed to get episodes by feed: {}", e)
+        })
+}
+
 // Tauri命令：获取特定RSS源的文章
 #[tauri::command(async)]
 async fn get_articles_by_feed(
@@ -1049,6 +1684,62 @@ async fn delete_article(app_state: State<'_, AppState>, article_id: i64) -> Resu
     })
 }
 
+// Tauri命令：获取文章的历史版本（发布者修改文章前的内容）
+#[tauri::command(async, rename_all = "camelCase")]
+async fn get_article_revisions(
+    app_state: State<'_, AppState>,
+    article_id: i64,
+) -> Result<Vec<ArticleRevision>, String> {
+    let db_manager = app_state.db_manager.lock().await;
+    db_manager.get_article_revisions(article_id).map_err(|e| {
+        eprintln!("Failed to get article revisions from database: {}", e);
+        format!("Failed to get article revisions: {}", e)
+    })
+}
+
+// Tauri命令：比较文章的两个版本
+// from_revision为None时使用最近的历史版本，to_revision为None时使用文章当前内容
+#[tauri::command(async, rename_all = "camelCase")]
+async fn get_article_diff(
+    app_state: State<'_, AppState>,
+    article_id: i64,
+    from_revision: Option<i64>,
+    to_revision: Option<i64>,
+) -> Result<ArticleDiff, String> {
+    let db_manager = app_state.db_manager.lock().await;
+    let map_db_error = |e: rusqlite::Error| {
+        eprintln!("Failed to get article version from database: {}", e);
+        format!("Failed to get article diff: {}", e)
+    };
+
+    let from_revision = match from_revision {
+        Some(revision_id) => revision_id,
+        None => db_manager
+            .get_article_revisions(article_id)
+            .map_err(map_db_error)?
+            .first()
+            .map(|revision| revision.id)
+            .ok_or_else(|| "文章没有历史版本".to_string())?,
+    };
+    let (old_title, old_content) = db_manager
+        .get_article_version(article_id, Some(from_revision))
+        .map_err(map_db_error)?;
+    let (new_title, new_content) = db_manager
+        .get_article_version(article_id, to_revision)
+        .map_err(map_db_error)?;
+
+    Ok(ArticleDiff {
+        article_id,
+        from_revision: Some(from_revision),
+        to_revision,
+        title: diff::diff_lines(&[old_title], &[new_title]),
+        content: diff::diff_lines(
+            &diff::html_to_paragraphs(&old_content),
+            &diff::html_to_paragraphs(&new_content),
+        ),
+    })
+}
+
 // Tauri命令：标记所有文章为已读
 #[tauri::command(async, rename_all = "camelCase")]
 async fn mark_all_articles_as_read(
@@ -1065,6 +1756,9 @@ async fn mark_all_articles_as_read(
 // Tauri命令：打开链接
 #[tauri::command]
 fn open_link(app_handle: tauri::AppHandle, url: String) -> Result<(), String> {
+    // 打开前去除跟踪参数，避免向目标网站泄露来源
+    let url = link_rules(&read_config_file()).canonicalize(&url, None);
+
     // 使用Tauri插件打开链接
     app_handle
         .opener()
@@ -1161,6 +1855,7 @@ async fn update_update_interval(
     if config.update.is_none() {
         config.update = Some(UpdateConfig {
             interval: Some(interval),
+            ..Default::default()
         });
     } else {
         if let Some(update_config) = &mut config.update {
@@ -1541,7 +2236,7 @@ async fn update_all_feeds(
 
     // 筛选需要更新的RSS源：
     // 1. 没有失败记录的源
-    // 2. 有失败记录但下次重试时间已到的源
+    // 2. 有失败记录或调度提示（Retry-After、TTL）但下次拉取时间已到的源
     let now = Utc::now();
     let feeds_to_update: Vec<Feed> = all_feeds
         .into_iter()
@@ -1559,26 +2254,45 @@ async fn update_all_feeds(
         return Ok(());
     }
 
+    // 读取并发更新限制和浏览器回退选项
+    let config = read_config_file();
+    let update_limits = config
+        .update
+        .as_ref()
+        .map(|update_config| update_config.limits())
+        .unwrap_or_default();
+
     // 克隆RSS更新器并更新所有需要更新的源
     let rss_updater = {
         let rss_updater = app_state.rss_updater.lock().await;
-        rss_updater.clone()
+        rss_updater
+            .clone()
+            .with_limits(update_limits)
+            .with_browser(browser_options(&config))
+            .with_network(network_options(&config))
+            .with_link_rules(link_rules(&config))
+            .with_exec(exec_fetcher(&config))
     };
     let results = rss_updater.update_feeds(&feeds_to_update).await;
 
     // 保存新文章到数据库，每个RSS源独立处理，每次操作后释放锁
-    for (index, result) in results.iter().enumerate() {
+    for (index, result) in results.into_iter().enumerate() {
         let feed = &feeds_to_update[index];
         match result {
-            Ok((_, articles)) => {
+            Ok((_, mut update)) => {
+                // 源已永久迁移时更新地址
+                let feed = &apply_permanent_redirect(&app_state, feed, &mut update).await;
+                save_websub_hub(&app_state, feed, &update).await;
+                fetch_full_content(&app_state, &rss_updater, feed, &mut update).await;
+
                 println!(
                     "开始保存来自 {} 的文章，共 {} 篇",
                     feed.name,
-                    articles.len()
+                    update.articles.len()
                 );
 
                 // 保存当前RSS源的所有文章
-                for article in articles {
+                for article in &update.articles {
                     // 为每篇文章独立获取和释放锁，减少锁持有时间
                     let db_manager = app_state.db_manager.lock().await;
                     // 尝试添加文章，如果成功则说明是新文章
@@ -1608,10 +2322,22 @@ async fn update_all_feeds(
                 let last_updated = Utc::now();
                 if let Err(e) = {
                     let mut db_manager = app_state.db_manager.lock().await;
-                    db_manager.update_feed_success(feed.id, last_updated)
+                    db_manager.update_feed_success(feed.id, last_updated, update.next_update_time)
                 } {
                     eprintln!("Failed to update feed success status: {}", e);
                 }
+
+                // 保存缓存验证头，供下次条件请求使用
+                if let Err(e) = {
+                    let mut db_manager = app_state.db_manager.lock().await;
+                    db_manager.update_feed_cache_headers(
+                        feed.id,
+                        update.etag.as_deref(),
+                        update.last_modified.as_deref(),
+                    )
+                } {
+                    eprintln!("Failed to update feed cache headers: {}", e);
+                }
             }
             Err(e) => {
                 eprintln!("Failed to update feed {}: {}", feed.name, e);
@@ -1619,7 +2345,7 @@ async fn update_all_feeds(
                 // 更新feed的失败状态
                 if let Err(err) = {
                     let mut db_manager = app_state.db_manager.lock().await;
-                    db_manager.update_feed_failure(feed.id, &e.to_string())
+                    db_manager.update_feed_failure(feed.id, &e)
                 } {
                     eprintln!("Failed to update feed failure status: {}", err);
                 }
@@ -1638,10 +2364,10 @@ async fn auto_update_feeds_task(app: tauri::AppHandle) {
 
     loop {
         match process_auto_update(&app).await {
-            Ok(update_interval) => {
-                // 使用配置的更新间隔
-                println!("下次自动更新检查将在 {} 秒后进行", update_interval);
-                sleep(Duration::from_secs(update_interval)).await;
+            Ok(delay) => {
+                // 等到最早到期的源，最长为配置的更新间隔
+                println!("下次自动更新检查将在 {} 秒后进行", delay);
+                sleep(Duration::from_secs(delay)).await;
             }
             Err(e) => {
                 eprintln!("自动更新处理失败: {}", e);
@@ -1727,27 +2453,86 @@ async fn process_auto_update(app: &tauri::AppHandle) -> Result<u64, Box<dyn std:
     let total_feeds_count = all_feeds.len();
     println!("发现 {} 个RSS源", total_feeds_count);
 
-    // 筛选需要更新的RSS源：
-    // 1. 没有失败记录的源
-    // 2. 有失败记录但下次重试时间已到的源
+    // 自适应模式下，统计各源最近的发文间隔
     let now = Utc::now();
+    let adaptive = config
+        .update
+        .as_ref()
+        .and_then(|update_config| update_config.adaptive)
+        .unwrap_or(false);
+    let posting_intervals = if adaptive {
+        let db_manager = app_state.db_manager.lock().await;
+        db_manager
+            .get_feed_posting_intervals(now - chrono::Duration::days(ADAPTIVE_WINDOW_DAYS))
+            .unwrap_or_else(|e| {
+                eprintln!("Failed to get feed posting intervals: {}", e);
+                std::collections::HashMap::new()
+            })
+    } else {
+        std::collections::HashMap::new()
+    };
+
+    // WebSub推送订阅生效的源只需低频轮询作为后备
+    let (websub_feeds, websub_fallback_interval) = match websub_config(&config) {
+        Some(websub_config) => {
+            let db_manager = app_state.db_manager.lock().await;
+            let feed_ids = db_manager
+                .get_active_websub_feed_ids(now)
+                .unwrap_or_else(|e| {
+                    eprintln!("Failed to get active WebSub subscriptions: {}", e);
+                    std::collections::HashSet::new()
+                });
+            (feed_ids, websub_config.fallback_interval())
+        }
+        None => (std::collections::HashSet::new(), 0),
+    };
+
+    // 源的更新间隔：源自身设置优先，其次为自适应间隔和全局更新间隔，
+    // 推送订阅生效的源不短于后备轮询间隔
+    let feed_interval = |feed: &Feed| {
+        let interval = feed
+            .update_interval
+            .or_else(|| {
+                adaptive.then(|| adaptive_update_interval(posting_intervals.get(&feed.id).copied()))
+            })
+            .unwrap_or(update_interval as i64);
+        if websub_feeds.contains(&feed.id) {
+            interval.max(websub_fallback_interval)
+        } else {
+            interval
+        }
+    };
+
+    // 筛选需要更新的RSS源：下次重试或调度提示（Retry-After、TTL）时间已到，
+    // 且距上次更新已超过该源更新间隔的源，预留少量余量避免刚好错过
     let feeds_to_update: Vec<Feed> = all_feeds
         .into_iter()
-        .filter(|feed| {
-            match &feed.next_retry_time {
-                None => true,                           // 没有失败记录，正常更新
-                Some(retry_time) => *retry_time <= now, // 下次重试时间已到
-            }
+        .filter(|feed| match feed_due_time(feed, feed_interval(feed)) {
+            Some(due) => due <= now + chrono::Duration::seconds(AUTO_UPDATE_MIN_DELAY),
+            None => true,
         })
         .collect();
 
     if !feeds_to_update.is_empty() {
         println!("需要更新 {} 个RSS源", feeds_to_update.len());
         
+        // 读取并发更新限制
+        let update_limits = config
+            .update
+            .as_ref()
+            .map(|update_config| update_config.limits())
+            .unwrap_or_default();
+
         // 获取RSS更新器 - 缩短锁持有时间
         let rss_updater = {
             let rss_updater = app_state.rss_updater.lock().await;
-            rss_updater.clone()
+            rss_updater
+                .clone()
+                .with_limits(update_limits)
+                .with_browser(browser_options(&config))
+                .with_network(network_options(&config))
+                .with_link_rules(link_rules(&config))
+                .with_exec(exec_fetcher(&config))
         };
 
         // 更新需要更新的RSS源
@@ -1759,17 +2544,30 @@ async fn process_auto_update(app: &tauri::AppHandle) -> Result<u64, Box<dyn std:
         println!("RSS源更新完成，耗时: {:.2?}", elapsed);
 
         // 处理更新结果
-        for (index, result) in results.iter().enumerate() {
+        for (index, result) in results.into_iter().enumerate() {
             let feed = &feeds_to_update[index];
             match result {
-                Ok((_, articles)) => {
-                    println!("成功获取来自 {} 的 {} 篇文章", feed.name, articles.len());
+                Ok((_, mut update)) => {
+                    // 源已永久迁移时更新地址
+                    let feed = &apply_permanent_redirect(&app_state, feed, &mut update).await;
+                    save_websub_hub(&app_state, feed, &update).await;
+                    fetch_full_content(&app_state, &rss_updater, feed, &mut update).await;
+
+                    println!(
+                        "成功获取来自 {} 的 {} 篇文章",
+                        feed.name,
+                        update.articles.len()
+                    );
 
                     // 更新feed的成功状态 - 立即释放锁
                     let last_updated = Utc::now();
                     if let Err(e) = {
                         let mut db_manager = app_state.db_manager.lock().await;
-                        db_manager.update_feed_success(feed.id, last_updated)
+                        db_manager.update_feed_success(
+                            feed.id,
+                            last_updated,
+                            update.next_update_time,
+                        )
                     } {
                         eprintln!(
                             "Failed to update feed success status for {}: {}",
@@ -1777,10 +2575,30 @@ async fn process_auto_update(app: &tauri::AppHandle) -> Result<u64, Box<dyn std:
                         );
                     }
 
+                    // 保存缓存验证头，供下次条件请求使用
+                    if let Err(e) = {
+                        let mut db_manager = app_state.db_manager.lock().await;
+                        db_manager.update_feed_cache_headers(
+                            feed.id,
+                            update.etag.as_deref(),
+                            update.last_modified.as_deref(),
+                        )
+                    } {
+                        eprintln!(
+                            "Failed to update feed cache headers for {}: {}",
+                            feed.name, e
+                        );
+                    }
+
+                    // 内容未修改（304），没有需要处理的文章
+                    if update.not_modified {
+                        continue;
+                    }
+
                     // 异步处理文章保存和翻译，避免阻塞主循环
                     let app_clone = app.clone();
                     let feed_clone = feed.clone();
-                    let articles_clone = articles.clone();
+                    let articles_clone = update.articles.clone();
 
                     tauri::async_runtime::spawn(async move {
                         process_articles_sync(app_clone, feed_clone, articles_clone).await;
@@ -1796,7 +2614,7 @@ async fn process_auto_update(app: &tauri::AppHandle) -> Result<u64, Box<dyn std:
                     // 更新feed的失败状态 - 立即释放锁
                     if let Err(update_e) = {
                         let mut db_manager = app_state.db_manager.lock().await;
-                        db_manager.update_feed_failure(feed.id, &error_msg)
+                        db_manager.update_feed_failure(feed.id, &e)
                     } {
                         eprintln!(
                             "Failed to update feed failure status for {}: {}",
@@ -1817,8 +2635,283 @@ async fn process_auto_update(app: &tauri::AppHandle) -> Result<u64, Box<dyn std:
         println!("更新上次更新时间: {}", last_update_time_guard);
     }
     
+    // 根据更新后各源的下次更新时间决定下次检查时间
+    let delay = match {
+        let db_manager = app_state.db_manager.lock().await;
+        db_manager.get_all_feeds()
+    } {
+        Ok(feeds) => next_auto_update_delay(
+            feeds
+                .iter()
+                .filter_map(|feed| feed_due_time(feed, feed_interval(feed))),
+            Utc::now(),
+            update_interval,
+        ),
+        Err(e) => {
+            eprintln!("Failed to get feeds for next auto-update: {}", e);
+            update_interval
+        }
+    };
+
     println!("自动更新周期完成");
-    Ok(update_interval)
+    Ok(delay)
+}
+
+/// WebSub回调处理：确认订阅意图，并将推送的内容交给文章处理流程
+struct AppWebSubHandler {
+    app: tauri::AppHandle,
+}
+
+#[async_trait]
+impl CallbackHandler for AppWebSubHandler {
+    async fn verify_intent(&self, token: &str, intent: &IntentVerification) -> bool {
+        let app_state = self.app.state::<AppState>();
+        let db_manager = app_state.db_manager.lock().await;
+        let subscription = match db_manager.get_websub_subscription_by_token(token) {
+            Ok(Some(subscription)) => subscription,
+            Ok(None) => return false,
+            Err(e) => {
+                eprintln!("Failed to get WebSub subscription: {}", e);
+                return false;
+            }
+        };
+        if intent.topic != subscription.topic {
+            return false;
+        }
+
+        let result = match intent.mode.as_str() {
+            "subscribe" => {
+                println!(
+                    "WebSub订阅已确认: {} (有效期 {:?} 秒)",
+                    subscription.topic, intent.lease_seconds
+                );
+                // 有效期来自推送中心的请求参数，限制在合理范围内，避免时间计算溢出
+                let lease_expires = intent.lease_seconds.and_then(|seconds| {
+                    chrono::Duration::try_seconds(seconds.clamp(0, WEBSUB_MAX_LEASE))
+                        .and_then(|lease| Utc::now().checked_add_signed(lease))
+                });
+                db_manager.activate_websub_subscription(token, lease_expires)
+            }
+            "denied" => {
+                println!(
+                    "WebSub推送中心拒绝订阅 {}: {}",
+                    subscription.topic,
+                    intent.reason.as_deref().unwrap_or("未说明原因")
+                );
+                db_manager.deny_websub_subscription(token, intent.reason.as_deref())
+            }
+            // 不会主动取消订阅，其他请求均不确认
+            _ => return false,
+        };
+        if let Err(e) = result {
+            eprintln!("Failed to update WebSub subscription: {}", e);
+            return false;
+        }
+        true
+    }
+
+    async fn deliver(
+        &self,
+        token: &str,
+        signature: Option<&str>,
+        content_type: Option<&str>,
+        content: Vec<u8>,
+    ) -> bool {
+        let app_state = self.app.state::<AppState>();
+        let (subscription, feed) = {
+            let db_manager = app_state.db_manager.lock().await;
+            let subscription = match db_manager.get_websub_subscription_by_token(token) {
+                Ok(Some(subscription)) => subscription,
+                Ok(None) => return false,
+                Err(e) => {
+                    eprintln!("Failed to get WebSub subscription: {}", e);
+                    return false;
+                }
+            };
+            match db_manager.get_feed_by_id(subscription.feed_id) {
+                Ok(feed) => (subscription, feed),
+                Err(e) => {
+                    eprintln!("Failed to get feed for WebSub subscription: {}", e);
+                    return false;
+                }
+            }
+        };
+
+        // 签名缺失或无效的内容直接丢弃，但仍然回应成功
+        let signature_valid = signature.is_some_and(|signature| {
+            websub::verify_signature(&subscription.secret, signature, &content)
+        });
+        if !signature_valid {
+            eprintln!("WebSub推送内容签名无效，已忽略: {}", feed.name);
+            return true;
+        }
+
+        // 在后台处理，尽快回应推送中心
+        let app = self.app.clone();
+        let content_type = content_type.map(|content_type| content_type.to_string());
+        tauri::async_runtime::spawn(async move {
+            process_websub_content(app, feed, content, content_type).await;
+        });
+        true
+    }
+}
+
+/// 处理WebSub推送的源内容，与自动更新使用相同的文章处理流程
+async fn process_websub_content(
+    app: tauri::AppHandle,
+    feed: Feed,
+    content: Vec<u8>,
+    content_type: Option<String>,
+) {
+    let app_state = app.state::<AppState>();
+    let rss_updater = configured_rss_updater(&read_config_file());
+    let mut update =
+        match rss_updater.parse_pushed_content(&feed, &content, content_type.as_deref()) {
+            Ok(update) => update,
+            Err(e) => {
+                eprintln!("Failed to parse WebSub content for {}: {}", feed.name, e);
+                return;
+            }
+        };
+    println!(
+        "收到WebSub推送: {}，{} 篇文章",
+        feed.name,
+        update.articles.len()
+    );
+
+    save_websub_hub(&app_state, &feed, &update).await;
+    fetch_full_content(&app_state, &rss_updater, &feed, &mut update).await;
+    {
+        let mut db_manager = app_state.db_manager.lock().await;
+        if let Err(e) = db_manager.update_feed_success(feed.id, Utc::now(), update.next_update_time)
+        {
+            eprintln!("Failed to update feed success status: {}", e);
+        }
+    }
+
+    process_articles_sync(app.clone(), feed.clone(), update.articles).await;
+    if let Err(e) = app.emit("feed_updated", Some(feed.id)) {
+        eprintln!("Failed to emit feed_updated event: {}", e);
+    }
+}
+
+// WebSub推送订阅任务：启动回调服务器，定期订阅新发现的推送中心并在到期前续订
+async fn websub_task(app: tauri::AppHandle) {
+    use tokio::time::{Duration, sleep};
+
+    // 回调服务器只在启动时根据配置决定是否运行
+    let config = read_config_file();
+    let Some(websub_config) = websub_config(&config) else {
+        return;
+    };
+    let listen = websub_config.listen();
+    let listener = match tokio::net::TcpListener::bind(&listen).await {
+        Ok(listener) => listener,
+        Err(e) => {
+            eprintln!("WebSub回调服务器启动失败 {}: {}", listen, e);
+            return;
+        }
+    };
+    println!("WebSub回调服务器已启动: {}", listen);
+    let handler = Arc::new(AppWebSubHandler { app: app.clone() });
+    tauri::async_runtime::spawn(websub::serve(listener, handler));
+
+    loop {
+        renew_websub_subscriptions(&app).await;
+        sleep(Duration::from_secs(WEBSUB_CHECK_INTERVAL)).await;
+    }
+}
+
+/// 向推送中心发送订阅请求：新发现的、等待确认超时的和即将到期的订阅
+async fn renew_websub_subscriptions(app: &tauri::AppHandle) {
+    let config = read_config_file();
+    let Some(websub_config) = websub_config(&config) else {
+        return;
+    };
+    let app_state = app.state::<AppState>();
+    let subscriptions = {
+        let db_manager = app_state.db_manager.lock().await;
+        match db_manager.get_websub_subscriptions() {
+            Ok(subscriptions) => subscriptions,
+            Err(e) => {
+                eprintln!("Failed to get WebSub subscriptions: {}", e);
+                return;
+            }
+        }
+    };
+
+    let builder = reqwest::Client::builder().timeout(std::time::Duration::from_secs(30));
+    let client = match network_options(&config)
+        .apply(builder, true)
+        .and_then(|builder| builder.build().map_err(AppError::from))
+    {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("Failed to create WebSub client: {}", e);
+            return;
+        }
+    };
+
+    let now = Utc::now();
+    let pending_timeout = chrono::Duration::seconds(WEBSUB_PENDING_TIMEOUT);
+    for subscription in subscriptions {
+        let request_timed_out = subscription
+            .last_requested
+            .is_none_or(|requested| now - requested > pending_timeout);
+        let needs_request = match subscription.state.as_str() {
+            "active" => {
+                request_timed_out
+                    && subscription.lease_expires.is_some_and(|expires| {
+                        expires - now < chrono::Duration::seconds(WEBSUB_RENEW_BEFORE)
+                    })
+            }
+            // 被拒绝后不再重试，源的推送中心变化时会重置为pending
+            "denied" => false,
+            _ => request_timed_out,
+        };
+        if !needs_request {
+            continue;
+        }
+
+        println!(
+            "发送WebSub订阅请求: {} -> {}",
+            subscription.topic, subscription.hub
+        );
+        let callback = websub::callback_url(&websub_config.callback_base(), &subscription.token);
+        let error = websub::subscribe(
+            &client,
+            &subscription.hub,
+            &subscription.topic,
+            &callback,
+            &subscription.secret,
+            Some(websub_config.lease_seconds()),
+        )
+        .await
+        .err()
+        .map(|e| {
+            eprintln!("WebSub订阅请求失败 {}: {}", subscription.topic, e);
+            e.to_string()
+        });
+
+        let db_manager = app_state.db_manager.lock().await;
+        if let Err(e) =
+            db_manager.update_websub_request(subscription.feed_id, now, error.as_deref())
+        {
+            eprintln!("Failed to update WebSub subscription: {}", e);
+        }
+    }
+}
+
+// Tauri命令：获取所有WebSub推送订阅
+#[tauri::command(async)]
+async fn get_websub_subscriptions(
+    app_state: State<'_, AppState>,
+) -> Result<Vec<WebSubSubscription>, String> {
+    let db_manager = app_state.db_manager.lock().await;
+    db_manager.get_websub_subscriptions().map_err(|e| {
+        eprintln!("Failed to get WebSub subscriptions from database: {}", e);
+        format!("Failed to get WebSub subscriptions: {}", e)
+    })
 }
 
 // 异步处理文章保存和翻译 - 内部使用同步获取状态的版本
@@ -1838,7 +2931,7 @@ async fn process_articles_sync(app_handle: tauri::AppHandle, feed: Feed, article
             if feed.translate_enabled {
                 let needs_translation = match {
                     let db_manager = app_state.db_manager.lock().await;
-                    db_manager.article_needs_translation(article.feed_id, &article.link)
+                    db_manager.article_needs_translation(&article)
                 } {
                     Ok(needs) => needs,
                     Err(e) => {
@@ -1918,7 +3011,7 @@ async fn process_articles(
         if feed.translate_enabled {
             let needs_translation = match {
                 let db_manager = app_state.db_manager.lock().await;
-                db_manager.article_needs_translation(article.feed_id, &article.link)
+                db_manager.article_needs_translation(&article)
             } {
                 Ok(needs) => needs,
                 Err(e) => {
@@ -2183,7 +3276,14 @@ fn generate_default_config(_config: &Config) {
         }),
         update: Some(UpdateConfig {
             interval: Some(5 * 60), // 默认5分钟
+            ..Default::default()
         }),
+        browser: None,
+        network: None,
+        links: None,
+        backfill: None,
+        websub: None,
+        exec: None,
     };
 
     // 序列化配置为TOML格式
@@ -2300,7 +3400,7 @@ pub fn run() {
     }
 
     println!("正在初始化数据库，路径: {}", db_path);
-    let db_manager = match DbManager::new(&db_path) {
+    let mut db_manager = match DbManager::new(&db_path) {
         Ok(manager) => manager,
         Err(e) => {
             eprintln!("初始化数据库失败: {}", e);
@@ -2309,8 +3409,23 @@ pub fn run() {
         }
     };
 
+    // 链接规则变化后（包括升级后首次启动），按当前的规则清理已保存文章链接中的跟踪参数
+    match db_manager.canonicalize_article_links_if_changed(&link_rules(&config)) {
+        Ok(0) => {}
+        Ok(count) => println!("已规范化 {} 篇文章的链接", count),
+        Err(e) => eprintln!("规范化文章链接失败: {}", e),
+    }
+
     // 初始化RSS更新器
-    let rss_updater = RssUpdater::new();
+    let update_limits = config
+        .update
+        .as_ref()
+        .map(|update_config| update_config.limits())
+        .unwrap_or_default();
+    let rss_updater = configured_rss_updater(&config).with_limits(update_limits);
+
+    // AI接口使用与RSS获取相同的代理和证书设置
+    AI_TRANSLATOR.configure_network(&network_options(&config));
 
     // 计算初始化时间
     let init_time = start_time.elapsed();
@@ -2345,6 +3460,12 @@ pub fn run() {
             tauri::async_runtime::spawn(async move {
                 auto_update_feeds_task(app_handle).await;
             });
+
+            // 启用WebSub时启动回调服务器和订阅维护任务
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                websub_task(app_handle).await;
+            });
             Ok(())
         })
         // 添加应用退出事件处理
@@ -2361,7 +3482,11 @@ pub fn run() {
             init_db,
             init_ai_translator,
             add_feed,
+            preview_feed,
+            discover_feeds,
             get_all_feeds,
+            get_feed_health,
+            delete_feeds,
             get_unread_count,
             get_all_unread_counts,
             get_article_count,
@@ -2378,6 +3503,9 @@ pub fn run() {
             get_all_groups,
             get_feeds_by_group,
             get_articles_by_feed,
+            get_episodes_by_feed,
+            backfill_feed,
+            get_websub_subscriptions,
             get_all_articles,
             toggle_favorite,
             get_favorite_articles,
@@ -2396,6 +3524,8 @@ pub fn run() {
             set_default_ai_platform,
             delete_articles,
             delete_article,
+            get_article_revisions,
+            get_article_diff,
             open_link,
             update_update_interval,
             add_blacklist_keyword,
@@ -2413,3 +3543,49 @@ pub fn run() {
         .run(tauri::generate_context!())
         .expect("Error running app");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn adapts_update_interval_to_posting_frequency() {
+        // 每天发文时每小时拉取一次
+        assert_eq!(adaptive_update_interval(Some(24 * 60 * 60)), 60 * 60);
+        assert_eq!(adaptive_update_interval(Some(2 * 60 * 60)), 5 * 60);
+    }
+
+    #[test]
+    fn clamps_adaptive_update_interval() {
+        assert_eq!(adaptive_update_interval(Some(60)), ADAPTIVE_MIN_INTERVAL);
+        assert_eq!(adaptive_update_interval(Some(0)), ADAPTIVE_MIN_INTERVAL);
+        assert_eq!(
+            adaptive_update_interval(Some(30 * 24 * 60 * 60)),
+            ADAPTIVE_MAX_INTERVAL
+        );
+        // 没有足够的文章统计发文间隔时使用上限
+        assert_eq!(adaptive_update_interval(None), ADAPTIVE_MAX_INTERVAL);
+    }
+
+    #[test]
+    fn waits_for_earliest_due_feed() {
+        let now = Utc::now();
+        let after = |seconds| now + chrono::Duration::seconds(seconds);
+
+        assert_eq!(
+            next_auto_update_delay([after(600), after(120)], now, 3600),
+            120
+        );
+        // 不超过全局更新间隔，也不短于最短等待时间
+        assert_eq!(next_auto_update_delay([after(7200)], now, 3600), 3600);
+        assert_eq!(
+            next_auto_update_delay([after(1)], now, 3600),
+            AUTO_UPDATE_MIN_DELAY as u64
+        );
+        assert_eq!(
+            next_auto_update_delay([after(-60)], now, 300),
+            AUTO_UPDATE_MIN_DELAY as u64
+        );
+        assert_eq!(next_auto_update_delay([], now, 300), 300);
+    }
+}
@@ -0,0 +1,191 @@
+use crate::error::AppError;
+use crate::rss::{FetchResponse, RssFetcher, decode_content};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::Stdio;
+use tokio::process::Command;
+use tokio::time::Duration;
+
+/// 本地文件源地址的前缀
+const FILE_SCHEME: &str = "file://";
+/// 本地命令源地址的前缀，地址格式为exec:{命令名称}
+const EXEC_SCHEME: &str = "exec:";
+
+/// 去除地址前缀（不区分大小写），地址不以该前缀开头时返回None
+fn strip_scheme<'a>(url: &'a str, scheme: &str) -> Option<&'a str> {
+    let url = url.trim();
+    url.get(..scheme.len())
+        .filter(|prefix| prefix.eq_ignore_ascii_case(scheme))
+        .map(|_| &url[scheme.len()..])
+}
+
+/// 是否为本地文件源地址
+pub fn is_file_url(url: &str) -> bool {
+    strip_scheme(url, FILE_SCHEME).is_some()
+}
+
+/// 是否为本地命令源地址
+pub fn is_exec_url(url: &str) -> bool {
+    strip_scheme(url, EXEC_SCHEME).is_some()
+}
+
+/// 本地文件源获取器，读取file://地址指向的文件
+///
+/// 以文件修改时间作为Last-Modified，文件未修改时返回not_modified
+#[derive(Debug, Clone, Default)]
+pub struct FileFetcher;
+
+impl FileFetcher {
+    /// 将file://地址转换为本地路径
+    fn file_path(url: &str) -> Result<PathBuf, AppError> {
+        url::Url::parse(url.trim())?
+            .to_file_path()
+            .map_err(|_| AppError::Other(format!("无效的本地文件地址: {}", url)))
+    }
+}
+
+#[async_trait]
+impl RssFetcher for FileFetcher {
+    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+        let response = self.fetch_conditional(url, None, None).await?;
+        Ok(response.content)
+    }
+
+    async fn fetch_conditional(
+        &self,
+        url: &str,
+        _etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> Result<FetchResponse, AppError> {
+        let path = Self::file_path(url)?;
+        let read_error =
+            |e: std::io::Error| AppError::Other(format!("读取本地文件 {:?} 失败: {}", path, e));
+
+        let modified = tokio::fs::metadata(&path)
+            .await
+            .map_err(read_error)?
+            .modified()
+            .ok()
+            .map(|modified| DateTime::<Utc>::from(modified).to_rfc2822());
+        if modified.is_some() && modified.as_deref() == last_modified {
+            println!("本地文件未修改: {:?}", path);
+            return Ok(FetchResponse {
+                content: String::new(),
+                not_modified: true,
+                last_modified: modified,
+                ..Default::default()
+            });
+        }
+
+        let bytes = tokio::fs::read(&path).await.map_err(read_error)?;
+        Ok(FetchResponse {
+            content: decode_content(&bytes, None),
+            last_modified: modified,
+            ..Default::default()
+        })
+    }
+}
+
+/// 本地命令源的命令设置
+#[derive(Debug, Clone, Default)]
+pub struct ExecCommand {
+    /// 可执行文件
+    pub program: String,
+    /// 命令行参数
+    pub args: Vec<String>,
+    /// 工作目录，为None时使用应用的当前目录
+    pub dir: Option<PathBuf>,
+}
+
+/// 本地命令源获取器，运行配置的命令并将标准输出作为源内容
+///
+/// 地址中只包含命令名称，只能运行配置文件中设置的命令
+#[derive(Debug, Clone)]
+pub struct ExecFetcher {
+    commands: HashMap<String, ExecCommand>,
+    timeout: Duration,
+}
+
+impl ExecFetcher {
+    /// 命令运行时间上限的默认值
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+    pub fn new(commands: HashMap<String, ExecCommand>, timeout: Duration) -> Self {
+        Self { commands, timeout }
+    }
+
+    /// 从exec:地址中取出命令名称
+    fn command_name(url: &str) -> Option<&str> {
+        strip_scheme(url, EXEC_SCHEME)
+            .map(|name| name.trim_start_matches('/'))
+            .filter(|name| !name.is_empty())
+    }
+}
+
+impl Default for ExecFetcher {
+    fn default() -> Self {
+        Self::new(HashMap::new(), Self::DEFAULT_TIMEOUT)
+    }
+}
+
+#[async_trait]
+impl RssFetcher for ExecFetcher {
+    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+        let name = Self::command_name(url)
+            .ok_or_else(|| AppError::Other(format!("无效的本地命令源地址: {}", url)))?;
+        let command = self.commands.get(name).ok_or_else(|| {
+            AppError::Other(format!(
+                "未配置本地命令 {}，请在配置文件的[exec.commands]中添加",
+                name
+            ))
+        })?;
+
+        println!(
+            "运行本地命令源 {}: {} {:?}",
+            name, command.program, command.args
+        );
+        let mut process = Command::new(&command.program);
+        process
+            .args(&command.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // 超时后丢弃future时结束子进程
+            .kill_on_drop(true);
+        if let Some(dir) = &command.dir {
+            process.current_dir(dir);
+        }
+
+        let output = tokio::time::timeout(self.timeout, process.output())
+            .await
+            .map_err(|_| {
+                AppError::Timeout(format!(
+                    "本地命令 {} 运行超过 {} 秒",
+                    name,
+                    self.timeout.as_secs()
+                ))
+            })?
+            .map_err(|e| AppError::Other(format!("启动本地命令 {} 失败: {}", name, e)))?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(AppError::Other(format!(
+                "本地命令 {} 运行失败 ({}): {}",
+                name,
+                output.status,
+                stderr.trim()
+            )));
+        }
+        if !output.stderr.is_empty() {
+            eprintln!(
+                "本地命令 {} 的错误输出: {}",
+                name,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+
+        Ok(decode_content(&output.stdout, None))
+    }
+}
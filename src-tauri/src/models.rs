@@ -1,5 +1,9 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::HashMap;
+
+/// 发送到前端时代替密钥的占位符，保存时收到占位符表示保留原来的值
+pub const REDACTED_SECRET: &str = "********";
 
 /// RSS源数据模型
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -14,6 +18,121 @@ pub struct Feed {
     pub last_update_status: Option<String>,
     pub update_attempts: i32,
     pub next_retry_time: Option<DateTime<Utc>>,
+    /// 上次响应的ETag，用于条件请求
+    pub etag: Option<String>,
+    /// 上次响应的Last-Modified，用于条件请求
+    pub last_modified: Option<String>,
+    /// 自动更新间隔（秒），为None时使用全局设置
+    pub update_interval: Option<i64>,
+    /// 上次更新失败的错误类型（dns/tls/timeout/http/parse等）
+    pub last_error_kind: Option<String>,
+    /// 上次更新失败时的HTTP状态码
+    pub last_http_status: Option<u16>,
+    /// 始终使用浏览器获取（用于会拦截普通请求的源）
+    #[serde(default)]
+    pub always_use_browser: bool,
+    /// 自定义请求设置（请求头、Cookie、认证信息），不会导出到OPML
+    #[serde(default)]
+    pub request_options: FeedRequestOptions,
+    /// 解析后下载文章网页并提取正文，用于只提供摘要的源
+    #[serde(default)]
+    pub fetch_full_content: bool,
+}
+
+/// RSS源的自定义请求设置，用于需要登录或令牌的私有源和内网源
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct FeedRequestOptions {
+    /// 额外的请求头，认证相关的请求头的值发送到前端时以占位符代替
+    #[serde(serialize_with = "serialize_redacted_headers")]
+    pub headers: HashMap<String, String>,
+    /// Cookie请求头的值
+    #[serde(serialize_with = "serialize_redacted")]
+    pub cookie: Option<String>,
+    /// Basic认证用户名和密码
+    pub username: Option<String>,
+    #[serde(serialize_with = "serialize_redacted")]
+    pub password: Option<String>,
+    /// Bearer令牌
+    #[serde(serialize_with = "serialize_redacted")]
+    pub bearer_token: Option<String>,
+    /// 自定义User-Agent，为None时使用默认值
+    pub user_agent: Option<String>,
+    /// 不使用全局代理，直接连接
+    pub bypass_proxy: bool,
+}
+
+impl FeedRequestOptions {
+    /// 是否没有任何自定义设置
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 是否为包含认证信息的请求头
+    pub fn is_secret_header(name: &str) -> bool {
+        let name = name.to_ascii_lowercase();
+        [
+            "auth", "cookie", "token", "key", "secret", "session", "password",
+        ]
+        .iter()
+        .any(|keyword| name.contains(keyword))
+    }
+
+    /// 将前端传回的占位符替换为已保存的值
+    ///
+    /// 前端只能看到占位符，未修改的密钥会原样传回；已保存的值中没有对应项时去掉该项
+    pub fn restore_secrets(&mut self, stored: &FeedRequestOptions) {
+        let restore = |value: &mut Option<String>, stored: &Option<String>| {
+            if value.as_deref() == Some(REDACTED_SECRET) {
+                *value = stored.clone();
+            }
+        };
+        restore(&mut self.cookie, &stored.cookie);
+        restore(&mut self.password, &stored.password);
+        restore(&mut self.bearer_token, &stored.bearer_token);
+
+        self.headers.retain(|name, value| {
+            if value != REDACTED_SECRET {
+                return true;
+            }
+            match stored.headers.get(name) {
+                Some(stored_value) => {
+                    *value = stored_value.clone();
+                    true
+                }
+                None => false,
+            }
+        });
+    }
+}
+
+/// 以占位符代替密钥序列化，不向前端发送实际的值
+fn serialize_redacted<S: Serializer>(
+    value: &Option<String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    value
+        .as_ref()
+        .map(|_| REDACTED_SECRET)
+        .serialize(serializer)
+}
+
+/// 序列化请求头，认证相关的请求头的值以占位符代替
+fn serialize_redacted_headers<S: Serializer>(
+    headers: &HashMap<String, String>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            if FeedRequestOptions::is_secret_header(name) {
+                (name, REDACTED_SECRET)
+            } else {
+                (name, value.as_str())
+            }
+        })
+        .collect::<HashMap<_, _>>()
+        .serialize(serializer)
 }
 
 /// 文章数据模型
@@ -32,6 +151,100 @@ pub struct Article {
     pub categories: Vec<String>,
     pub translated_title: Option<String>,
     pub translated_content: Option<String>,
+    /// 源中的原始摘要（抓取全文后content为完整正文）
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// 源提供的唯一标识（RSS的<guid>、Atom的<id>或JSON Feed的id）
+    #[serde(default)]
+    pub guid: Option<String>,
+    /// 最后修改时间（Atom的<updated>或JSON Feed的date_modified），
+    /// 源未提供时为检测到文章被修改的时间
+    #[serde(default)]
+    pub updated: Option<DateTime<Utc>>,
+    /// 首次获取到文章的时间，源中没有发布时间的文章以此作为发布时间
+    #[serde(default)]
+    pub first_seen: Option<DateTime<Utc>>,
+    /// 音频、视频等媒体附件（播客节目）
+    #[serde(default)]
+    pub enclosures: Vec<Enclosure>,
+    /// 源中原始标题和内容的哈希（清理HTML、修复链接和黑名单替换之前），用于检测发布者修改
+    #[serde(skip)]
+    pub source_hash: Option<String>,
+}
+
+/// 根据标题和内容计算稳定的哈希（FNV-1a）
+pub fn content_hash(title: &str, content: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in title.bytes().chain([0]).chain(content.bytes()) {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("hash:{:016x}", hash)
+}
+
+/// 文章的媒体附件，来自RSS的<enclosure>、Atom的enclosure链接、Media RSS或JSON Feed的attachments
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct Enclosure {
+    #[serde(default)]
+    pub id: i64,
+    #[serde(default)]
+    pub article_id: i64,
+    pub url: String,
+    pub mime_type: Option<String>,
+    /// 文件大小（字节）
+    pub length: Option<i64>,
+    /// 时长（秒）
+    pub duration: Option<i64>,
+    /// 节目封面（itunes:image）
+    pub image: Option<String>,
+    /// 节目的集数和季数（itunes:episode、itunes:season）
+    pub episode: Option<i64>,
+    pub season: Option<i64>,
+}
+
+/// 文章的历史版本，文章被发布者修改时保存修改前的内容
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArticleRevision {
+    pub id: i64,
+    pub article_id: i64,
+    pub title: String,
+    pub content: String,
+    /// 该版本的最后修改时间，未知时为None
+    pub updated: Option<DateTime<Utc>>,
+    /// 该版本被新版本替换的时间
+    pub replaced_at: DateTime<Utc>,
+}
+
+/// 差异行的类型
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DiffKind {
+    /// 两个版本中相同
+    Equal,
+    /// 新版本中增加
+    Insert,
+    /// 新版本中删除
+    Delete,
+}
+
+/// 差异中的一行文字
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+/// 文章两个版本之间的差异
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ArticleDiff {
+    pub article_id: i64,
+    /// 旧版本的ID，为None时表示文章当前内容
+    pub from_revision: Option<i64>,
+    /// 新版本的ID，为None时表示文章当前内容
+    pub to_revision: Option<i64>,
+    pub title: Vec<DiffLine>,
+    /// 按段落比较的正文差异（已去除HTML标签）
+    pub content: Vec<DiffLine>,
 }
 
 /// 分组数据模型
@@ -50,6 +263,101 @@ pub struct OpmlFeed {
     pub category: Option<String>,
 }
 
+/// 自动发现的RSS源候选
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedCandidate {
+    pub url: String,
+    pub title: Option<String>,
+    /// 源格式：rss、atom或json
+    pub format: String,
+}
+
+/// RSS源预览结果，不写入数据库
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedPreview {
+    pub url: String,
+    /// 源格式：rss、atom或json
+    pub format: String,
+    pub title: Option<String>,
+    pub item_count: usize,
+    pub newest_date: Option<DateTime<Utc>>,
+    /// 前几篇文章
+    pub articles: Vec<Article>,
+}
+
+/// RSS源的WebSub推送订阅
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WebSubSubscription {
+    pub feed_id: i64,
+    /// 推送中心地址
+    pub hub: String,
+    /// 订阅的主题地址
+    pub topic: String,
+    /// 回调地址中标识该订阅的随机字符串
+    pub token: String,
+    /// 推送内容签名使用的密钥，不返回给前端
+    #[serde(skip_serializing, default)]
+    pub secret: String,
+    /// 订阅状态：pending（等待推送中心确认）、active（已生效）、denied（被拒绝）
+    pub state: String,
+    /// 订阅到期时间
+    pub lease_expires: Option<DateTime<Utc>>,
+    /// 上次发送订阅请求的时间
+    pub last_requested: Option<DateTime<Utc>>,
+    /// 上次订阅失败或被拒绝的原因
+    pub last_error: Option<String>,
+}
+
+/// 回溯历史文章的进度，通过backfill_progress事件发送给前端
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BackfillProgress {
+    pub feed_id: i64,
+    /// 已获取的页数
+    pub pages: usize,
+    /// 已获取的文章数
+    pub articles: usize,
+    /// 回溯已结束
+    pub done: bool,
+}
+
+/// RSS源健康状态分类
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedHealthStatus {
+    /// 正常更新且近期有新文章
+    Healthy,
+    /// 更新正常但长期没有新文章
+    Stale,
+    /// 最近一次更新失败
+    Failing,
+    /// 持续失败超过指定天数
+    Dead,
+}
+
+/// RSS源健康报告
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FeedHealth {
+    pub feed_id: i64,
+    pub name: String,
+    pub url: String,
+    /// 最后一次成功更新的时间
+    pub last_success_time: Option<DateTime<Utc>>,
+    /// 连续失败次数
+    pub update_attempts: i32,
+    /// 最后一次错误信息及类型
+    pub last_error: Option<String>,
+    pub last_error_kind: Option<String>,
+    pub last_http_status: Option<u16>,
+    pub next_retry_time: Option<DateTime<Utc>>,
+    /// 开始连续失败的时间
+    pub failing_since: Option<DateTime<Utc>>,
+    /// 最近12周平均每周文章数
+    pub articles_per_week: f64,
+    /// 最新文章的发布时间
+    pub newest_article_date: Option<DateTime<Utc>>,
+    pub status: FeedHealthStatus,
+}
+
 /// 搜索结果模型
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SearchResult {
@@ -68,3 +376,69 @@ pub struct AIPlatform {
     pub api_model: String,
     pub is_default: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> FeedRequestOptions {
+        FeedRequestOptions {
+            headers: HashMap::from([
+                ("Authorization".to_string(), "Basic abc".to_string()),
+                ("PRIVATE-TOKEN".to_string(), "token".to_string()),
+                ("Accept-Language".to_string(), "zh-CN".to_string()),
+            ]),
+            cookie: Some("session=1".to_string()),
+            username: Some("user".to_string()),
+            password: Some("password".to_string()),
+            bearer_token: Some("bearer".to_string()),
+            user_agent: None,
+            bypass_proxy: true,
+        }
+    }
+
+    #[test]
+    fn redacts_secrets_when_serialized() {
+        let json = serde_json::to_value(options()).unwrap();
+        assert_eq!(json["cookie"], REDACTED_SECRET);
+        assert_eq!(json["password"], REDACTED_SECRET);
+        assert_eq!(json["bearer_token"], REDACTED_SECRET);
+        assert_eq!(json["headers"]["Authorization"], REDACTED_SECRET);
+        assert_eq!(json["headers"]["PRIVATE-TOKEN"], REDACTED_SECRET);
+        assert_eq!(json["headers"]["Accept-Language"], "zh-CN");
+        assert_eq!(json["username"], "user");
+        assert!(json["user_agent"].is_null());
+        assert!(!json.to_string().contains("abc"));
+    }
+
+    #[test]
+    fn restores_unchanged_secrets() {
+        let stored = options();
+        let mut received: FeedRequestOptions =
+            serde_json::from_value(serde_json::to_value(&stored).unwrap()).unwrap();
+        received.restore_secrets(&stored);
+        assert_eq!(received, stored);
+    }
+
+    #[test]
+    fn keeps_new_and_cleared_secrets() {
+        let stored = options();
+        let mut received: FeedRequestOptions =
+            serde_json::from_value(serde_json::to_value(&stored).unwrap()).unwrap();
+        received.password = Some("new password".to_string());
+        received.cookie = None;
+        received.headers.remove("Authorization");
+        received
+            .headers
+            .insert("X-Api-Key".to_string(), REDACTED_SECRET.to_string());
+        received.restore_secrets(&stored);
+
+        assert_eq!(received.password.as_deref(), Some("new password"));
+        assert_eq!(received.cookie, None);
+        assert_eq!(received.bearer_token.as_deref(), Some("bearer"));
+        assert!(!received.headers.contains_key("Authorization"));
+        // 已保存的值中没有的占位符不会被当作密钥发送
+        assert!(!received.headers.contains_key("X-Api-Key"));
+        assert_eq!(received.headers["PRIVATE-TOKEN"], "token");
+    }
+}
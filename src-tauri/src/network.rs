@@ -0,0 +1,53 @@
+use crate::error::AppError;
+use reqwest::{Certificate, ClientBuilder, NoProxy, Proxy};
+use std::path::{Path, PathBuf};
+
+/// 网络设置：代理和自定义CA证书，同时用于RSS获取和AI接口
+#[derive(Debug, Clone, Default)]
+pub struct NetworkOptions {
+    /// 代理地址，支持http://、https://和socks5://，为None时使用系统环境变量中的代理
+    pub proxy: Option<String>,
+    /// 不使用代理的主机列表，逗号分隔，例如 "localhost,.corp.example.com,10.0.0.0/8"
+    pub no_proxy: Option<String>,
+    /// 额外信任的CA证书文件（PEM或DER格式），用于企业网络的TLS拦截
+    pub ca_certs: Vec<PathBuf>,
+}
+
+impl NetworkOptions {
+    /// 将代理和CA证书设置应用到客户端构建器
+    ///
+    /// use_proxy为false时不使用任何代理（包括环境变量中的代理），用于设置了绕过代理的RSS源
+    pub fn apply(
+        &self,
+        mut builder: ClientBuilder,
+        use_proxy: bool,
+    ) -> Result<ClientBuilder, AppError> {
+        for path in &self.ca_certs {
+            for cert in Self::read_certificates(path)? {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        if !use_proxy {
+            builder = builder.no_proxy();
+        } else if let Some(proxy_url) = self.proxy.as_deref().filter(|url| !url.is_empty()) {
+            let no_proxy = self.no_proxy.as_deref().and_then(NoProxy::from_string);
+            builder = builder.proxy(Proxy::all(proxy_url)?.no_proxy(no_proxy));
+        }
+
+        Ok(builder)
+    }
+
+    /// 读取CA证书文件，PEM文件可以包含多个证书
+    fn read_certificates(path: &Path) -> Result<Vec<Certificate>, AppError> {
+        let bytes = std::fs::read(path)
+            .map_err(|e| AppError::Other(format!("读取CA证书失败 {:?}: {}", path, e)))?;
+        let certs = Certificate::from_pem_bundle(&bytes).unwrap_or_default();
+        if !certs.is_empty() {
+            return Ok(certs);
+        }
+        Certificate::from_der(&bytes)
+            .map(|cert| vec![cert])
+            .map_err(|e| AppError::Other(format!("无效的CA证书 {:?}: {}", path, e)))
+    }
+}
@@ -1,11 +1,33 @@
-use crate::models::{Article, Feed};
+use crate::canonical::LinkRules;
+use crate::dates::parse_date;
+use crate::error::AppError;
+use crate::extractor::extract_main_content;
+use crate::local::{ExecFetcher, FileFetcher, is_exec_url, is_file_url};
+use crate::models::{
+    Article, BackfillProgress, Enclosure, Feed, FeedCandidate, FeedPreview, FeedRequestOptions,
+    content_hash,
+};
+use crate::network::NetworkOptions;
+use crate::sanitizer::{is_tracker_url, sanitize_html};
 use async_trait::async_trait;
 use atom_syndication::{Entry as AtomEntry, Feed as AtomFeed};
-use chrono::{DateTime, Utc};
+use base64::Engine;
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc, Weekday};
+use futures::StreamExt;
 use html_escape::decode_html_entities;
+use reqwest::header::{
+    AUTHORIZATION, CONTENT_TYPE, COOKIE, ETAG, HeaderMap, HeaderName, HeaderValue,
+    IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED, LOCATION, RETRY_AFTER, USER_AGENT,
+};
 use reqwest::{Client, StatusCode};
+use rss::extension::syndication::UpdatePeriod;
 use rss::{Channel, Item};
-use tokio::time::Duration;
+use serde::Deserialize;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::{Mutex, Semaphore};
+use tokio::time::{Duration, Instant};
 
 // 仅在非 Android/iOS 平台上使用 headless_chrome
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
@@ -13,60 +35,486 @@ use headless_chrome::{Browser, LaunchOptionsBuilder};
 #[cfg(not(any(target_os = "android", target_os = "ios")))]
 use std::ffi::OsStr;
 
+/// RSS获取结果
+#[derive(Debug, Clone, Default)]
+pub struct FetchResponse {
+    /// 响应内容（内容未修改时为空）
+    pub content: String,
+    /// 服务器返回304 Not Modified
+    pub not_modified: bool,
+    /// 响应中的ETag头
+    pub etag: Option<String>,
+    /// 响应中的Last-Modified头
+    pub last_modified: Option<String>,
+    /// 永久重定向（301/308）后的新地址
+    pub permanent_redirect: Option<String>,
+}
+
+/// Retry-After允许的最长等待时间，避免异常值导致源长期不更新
+const MAX_RETRY_AFTER_SECS: i64 = 24 * 60 * 60;
+
+/// 解析Retry-After头，支持秒数和HTTP日期两种格式
+fn parse_retry_after(value: &str) -> Option<DateTime<Utc>> {
+    let now = Utc::now();
+    let retry_after = if let Ok(seconds) = value.trim().parse::<i64>() {
+        now + chrono::Duration::seconds(seconds.max(0))
+    } else {
+        DateTime::parse_from_rfc2822(value.trim())
+            .ok()?
+            .with_timezone(&Utc)
+    };
+    Some(retry_after.min(now + chrono::Duration::seconds(MAX_RETRY_AFTER_SECS)))
+}
+
+/// 从Content-Type头中提取charset参数
+fn content_type_charset(content_type: &str) -> Option<String> {
+    content_type.split(';').skip(1).find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim().eq_ignore_ascii_case("charset").then(|| {
+            value
+                .trim()
+                .trim_matches(|c| c == '"' || c == '\'')
+                .to_string()
+        })
+    })
+}
+
+/// 查找XML声明（<?xml ... encoding="..."?>）中encoding属性值的位置
+fn xml_declaration_encoding_range(text: &str) -> Option<std::ops::Range<usize>> {
+    let trimmed = text.trim_start_matches('\u{feff}').trim_start();
+    let offset = text.len() - trimmed.len();
+    if !trimmed.starts_with("<?xml") {
+        return None;
+    }
+
+    let declaration = &trimmed[..trimmed.find("?>")?];
+    let attr_end = declaration.find("encoding")? + "encoding".len();
+    let after_eq = declaration[attr_end..]
+        .trim_start()
+        .strip_prefix('=')?
+        .trim_start();
+    let quote = after_eq
+        .chars()
+        .next()
+        .filter(|c| *c == '"' || *c == '\'')?;
+    let value_len = after_eq[1..].find(quote)?;
+
+    let value_start = offset + declaration.len() - after_eq.len() + 1;
+    Some(value_start..value_start + value_len)
+}
+
+/// 将响应字节解码为UTF-8文本
+///
+/// 编码优先级：BOM > Content-Type头中的charset > XML声明中的encoding，均未指定时按UTF-8解码。
+/// Content-Type声明的编码无法正确解码时，再尝试XML声明中的编码
+pub fn decode_content(bytes: &[u8], content_type: Option<&str>) -> String {
+    // 有BOM时直接按BOM指示的编码解码
+    if let Some((encoding, bom_len)) = encoding_rs::Encoding::for_bom(bytes) {
+        let (text, _) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+        return normalize_xml_declaration(text.into_owned());
+    }
+
+    // XML声明位于文档开头，只需检查前面一小部分内容
+    let head = String::from_utf8_lossy(&bytes[..bytes.len().min(1024)]);
+    let declared_encoding = xml_declaration_encoding_range(&head)
+        .and_then(|range| encoding_rs::Encoding::for_label(head[range].as_bytes()));
+    let header_encoding = content_type
+        .and_then(content_type_charset)
+        .and_then(|charset| encoding_rs::Encoding::for_label(charset.as_bytes()));
+
+    let encoding = header_encoding
+        .or(declared_encoding)
+        .unwrap_or(encoding_rs::UTF_8);
+    let (text, _, had_errors) = encoding.decode(bytes);
+
+    let text = match declared_encoding {
+        Some(declared) if had_errors && declared != encoding => {
+            println!(
+                "按 {} 解码失败，改用XML声明中的编码 {}",
+                encoding.name(),
+                declared.name()
+            );
+            declared.decode(bytes).0
+        }
+        _ => text,
+    };
+
+    normalize_xml_declaration(text.into_owned())
+}
+
+/// 转码后将XML声明中的encoding改为UTF-8，避免解析器按原编码再次解码
+fn normalize_xml_declaration(mut text: String) -> String {
+    if let Some(range) = xml_declaration_encoding_range(&text)
+        .filter(|range| !text[range.clone()].eq_ignore_ascii_case("utf-8"))
+    {
+        text.replace_range(range, "UTF-8");
+    }
+    text
+}
+
 /// RSS获取器特质
 #[async_trait]
 pub trait RssFetcher: Send + Sync {
     /// 获取RSS源内容
-    async fn fetch(&self, url: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>>;
+    async fn fetch(&self, url: &str) -> Result<String, AppError>;
+
+    /// 使用条件请求获取RSS源内容，默认实现忽略缓存验证头
+    async fn fetch_conditional(
+        &self,
+        url: &str,
+        _etag: Option<&str>,
+        _last_modified: Option<&str>,
+    ) -> Result<FetchResponse, AppError> {
+        let content = self.fetch(url).await?;
+        Ok(FetchResponse {
+            content,
+            ..Default::default()
+        })
+    }
+}
+
+/// 浏览器回退获取选项
+#[derive(Debug, Clone, Default)]
+pub struct BrowserOptions {
+    /// 浏览器可执行文件路径，为None时自动查找
+    pub path: Option<std::path::PathBuf>,
+    /// 使用真正的无头模式（部分网站会拦截无头浏览器，默认在屏幕外显示窗口）
+    pub headless: bool,
+}
+
+/// 指定浏览器路径的环境变量
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+const BROWSER_PATH_ENV: &str = "RSS_READER_BROWSER";
+
+/// 查找Chrome/Chromium/Edge浏览器可执行文件
+///
+/// 查找顺序：配置文件指定的路径 > RSS_READER_BROWSER环境变量 > CHROME环境变量及PATH中的
+/// chromium/google-chrome等 > 各平台的默认安装位置
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+fn find_browser_executable(configured: Option<&std::path::Path>) -> Option<std::path::PathBuf> {
+    if let Some(path) = configured {
+        if path.exists() {
+            return Some(path.to_path_buf());
+        }
+        eprintln!("配置的浏览器路径不存在: {:?}", path);
+    }
+
+    if let Some(path) = std::env::var_os(BROWSER_PATH_ENV).map(std::path::PathBuf::from) {
+        if path.exists() {
+            return Some(path);
+        }
+        eprintln!("{}指定的浏览器路径不存在: {:?}", BROWSER_PATH_ENV, path);
+    }
+
+    if let Ok(path) = headless_chrome::browser::default_executable() {
+        return Some(path);
+    }
+
+    // Windows下Chrome的默认安装位置
+    let program_files =
+        std::env::var("ProgramFiles").unwrap_or_else(|_| r"C:\Program Files".to_string());
+    let chrome_path =
+        std::path::PathBuf::from(program_files).join(r"Google\Chrome\Application\chrome.exe");
+    chrome_path.exists().then_some(chrome_path)
 }
 
 /// Reqwest RSS获取器
+#[derive(Clone)]
 pub struct ReqwestFetcher {
-    client: Client,
+    /// HTTP客户端，代理或证书配置无效时为错误信息
+    client: Result<Client, String>,
+    /// 不使用代理的客户端，用于设置了绕过代理的RSS源
+    direct_client: Result<Client, String>,
+    browser: BrowserOptions,
+    network: NetworkOptions,
 }
 
 impl ReqwestFetcher {
     pub fn new() -> Self {
-        let client = Client::builder()
+        let network = NetworkOptions::default();
+        Self {
+            client: Self::build_client(&network, true),
+            direct_client: Self::build_client(&network, false),
+            browser: BrowserOptions::default(),
+            network,
+        }
+    }
+
+    /// 设置代理和CA证书，重新创建HTTP客户端
+    pub fn with_network(mut self, network: NetworkOptions) -> Self {
+        self.client = Self::build_client(&network, true);
+        self.direct_client = Self::build_client(&network, false);
+        self.network = network;
+        self
+    }
+
+    fn build_client(network: &NetworkOptions, use_proxy: bool) -> Result<Client, String> {
+        let builder = Client::builder()
             .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/143.0.0.0 Safari/537.36")
             .timeout(Duration::from_secs(30))
-            .build()
-            .unwrap_or_else(|_| Client::new());
-        Self { client }
+            // 手动跟随重定向，以便识别永久重定向
+            .redirect(reqwest::redirect::Policy::none());
+        network
+            .apply(builder, use_proxy)
+            .and_then(|builder| builder.build().map_err(AppError::from))
+            .map_err(|e| {
+                // 代理或证书配置无效时不发送请求，避免绕过用户设置的代理或证书直接连接
+                eprintln!("应用网络设置失败: {}，修正配置前不会获取RSS源", e);
+                e.to_string()
+            })
+    }
+
+    /// 获取HTTP客户端，网络设置无效时返回错误
+    fn client(&self, bypass_proxy: bool) -> Result<&Client, AppError> {
+        let client = if bypass_proxy {
+            &self.direct_client
+        } else {
+            &self.client
+        };
+        client
+            .as_ref()
+            .map_err(|e| AppError::Other(format!("网络设置无效: {}", e)))
     }
 }
 
 impl ReqwestFetcher {
+    /// 直接获取页面内容，非成功状态码视为错误，不回退到Headless Chrome
+    pub async fn fetch_plain(&self, url: &str) -> Result<String, AppError> {
+        let (response, _) = self
+            .get_following_redirects(url, HeaderMap::new(), &FeedRequestOptions::default())
+            .await?;
+        if !response.status().is_success() {
+            return Err(AppError::Http {
+                status: response.status().as_u16(),
+                url: url.to_string(),
+            });
+        }
+        Self::read_text(response).await
+    }
+
+    /// 直接使用浏览器获取RSS源内容，用于已知会拦截普通请求的源
+    pub async fn fetch_with_browser(
+        &self,
+        url: &str,
+        options: &FeedRequestOptions,
+    ) -> Result<FetchResponse, AppError> {
+        // 浏览器同样使用配置的代理，网络设置无效时不启动浏览器
+        self.client(options.bypass_proxy)?;
+        let content = self
+            .fetch_with_headless_chrome(url, options)
+            .await
+            .map_err(|e| AppError::Other(format!("使用浏览器获取RSS源失败: {}", e)))?;
+        Ok(FetchResponse {
+            content,
+            ..Default::default()
+        })
+    }
+
+    /// 使用RSS源的自定义请求设置和缓存验证头获取内容
+    pub async fn fetch_with_options(
+        &self,
+        url: &str,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+        options: &FeedRequestOptions,
+    ) -> Result<FetchResponse, AppError> {
+        // 首先尝试使用reqwest获取内容，带上缓存验证头
+        let mut headers = HeaderMap::new();
+        if let Some(value) = etag.and_then(|etag| HeaderValue::from_str(etag).ok()) {
+            headers.insert(IF_NONE_MATCH, value);
+        }
+        if let Some(value) =
+            last_modified.and_then(|last_modified| HeaderValue::from_str(last_modified).ok())
+        {
+            headers.insert(IF_MODIFIED_SINCE, value);
+        }
+        let (response, permanent_redirect) =
+            self.get_following_redirects(url, headers, options).await?;
+
+        // 内容未修改，沿用已保存的缓存验证头
+        if response.status() == StatusCode::NOT_MODIFIED {
+            println!("RSS源内容未修改 (304): {}", url);
+            return Ok(FetchResponse {
+                content: String::new(),
+                not_modified: true,
+                etag: header_value(&response, ETAG).or_else(|| etag.map(|s| s.to_string())),
+                last_modified: header_value(&response, LAST_MODIFIED)
+                    .or_else(|| last_modified.map(|s| s.to_string())),
+                permanent_redirect,
+            });
+        }
+
+        // 服务器限流或暂时不可用，记录Retry-After后直接返回错误
+        if response.status() == StatusCode::TOO_MANY_REQUESTS
+            || response.status() == StatusCode::SERVICE_UNAVAILABLE
+        {
+            let retry_after =
+                header_value(&response, RETRY_AFTER).and_then(|value| parse_retry_after(&value));
+            println!("服务器要求稍后重试 ({}): {}", response.status(), url);
+            return Err(AppError::RateLimited {
+                status: response.status().as_u16(),
+                url: url.to_string(),
+                retry_after,
+            });
+        }
+
+        // 检查响应状态码
+        if response.status() == StatusCode::FORBIDDEN {
+            // 遇到403错误
+            println!("遇到403错误: {}", url);
+            
+            #[cfg(not(any(target_os = "android", target_os = "ios")))]
+            {
+                // 在非Android/iOS平台上，尝试使用Headless Chrome获取
+                println!("尝试使用Headless Chrome获取RSS源");
+                let content = self
+                    .fetch_with_headless_chrome(url, options)
+                    .await
+                    .map_err(|e| {
+                        eprintln!("Headless Chrome获取失败: {}", e);
+                        AppError::Http {
+                            status: StatusCode::FORBIDDEN.as_u16(),
+                            url: url.to_string(),
+                        }
+                    })?;
+                // 浏览器获取的是原地址，重定向链没有成功返回内容，不记录永久重定向
+                return Ok(FetchResponse {
+                    content,
+                    ..Default::default()
+                });
+            }
+            
+            #[cfg(any(target_os = "android", target_os = "ios"))]
+            {
+                // 在Android/iOS平台上，直接返回错误
+                // 因为Headless Chrome在移动平台上不可用
+                return Err(AppError::Http {
+                    status: StatusCode::FORBIDDEN.as_u16(),
+                    url: url.to_string(),
+                });
+            }
+        }
+
+        // 其他错误状态码（404、410、5xx等）
+        if !response.status().is_success() {
+            return Err(AppError::Http {
+                status: response.status().as_u16(),
+                url: url.to_string(),
+            });
+        }
+
+        // 正常响应，记录缓存验证头并返回内容
+        if let Some(new_url) = &permanent_redirect {
+            println!("RSS源已永久迁移: {} -> {}", url, new_url);
+        }
+        let etag = header_value(&response, ETAG);
+        let last_modified = header_value(&response, LAST_MODIFIED);
+        let content = Self::read_text(response).await?;
+        Ok(FetchResponse {
+            content,
+            not_modified: false,
+            etag,
+            last_modified,
+            permanent_redirect,
+        })
+    }
+
+    /// 读取响应的原始字节，根据BOM、Content-Type和XML声明检测编码并转码为UTF-8
+    async fn read_text(response: reqwest::Response) -> Result<String, AppError> {
+        let content_type = header_value(&response, CONTENT_TYPE);
+        let bytes = response.bytes().await?;
+        Ok(decode_content(&bytes, content_type.as_deref()))
+    }
+
+    /// 发送GET请求并手动跟随重定向
+    ///
+    /// 重定向链全部为永久重定向（301/308）时，同时返回最终地址。
+    /// 自定义请求头和认证信息只发送给与原地址同源的请求
+    async fn get_following_redirects(
+        &self,
+        url: &str,
+        headers: HeaderMap,
+        options: &FeedRequestOptions,
+    ) -> Result<(reqwest::Response, Option<String>), AppError> {
+        const MAX_REDIRECTS: usize = 10;
+
+        let origin = url::Url::parse(url)?.origin();
+        let mut current = url::Url::parse(url)?;
+        let mut redirected = false;
+        let mut all_permanent = true;
+
+        for _ in 0..=MAX_REDIRECTS {
+            let mut request_headers = request_headers(options, current.origin() == origin);
+            request_headers.extend(headers.clone());
+            let response = self
+                .client(options.bypass_proxy)?
+                .get(current.clone())
+                .headers(request_headers)
+                .send()
+                .await?;
+
+            let status = response.status();
+            let location = if status.is_redirection() && status != StatusCode::NOT_MODIFIED {
+                header_value(&response, LOCATION)
+            } else {
+                None
+            };
+
+            // 不是重定向，返回最终响应
+            let Some(location) = location else {
+                let permanent_redirect = (redirected && all_permanent).then(|| current.to_string());
+                return Ok((response, permanent_redirect));
+            };
+
+            all_permanent &=
+                status == StatusCode::MOVED_PERMANENTLY || status == StatusCode::PERMANENT_REDIRECT;
+            redirected = true;
+            current = current.join(&location)?;
+            println!("跟随重定向 ({}): {}", status, current);
+        }
+
+        Err(AppError::Other(format!("重定向次数过多 ({})", url)))
+    }
+
     /// 使用Headless Chrome获取RSS源内容（仅在非Android/iOS平台可用）
     #[cfg(not(any(target_os = "android", target_os = "ios")))]
     async fn fetch_with_headless_chrome(
         &self,
         url: &str,
+        options: &FeedRequestOptions,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         println!("尝试使用Headless Chrome获取RSS源: {}", url);
 
-        // 构建浏览器启动选项，使用非无头模式以避免403错误
-        // 并添加命令行参数，尽量隐藏浏览器窗口
-        let chrome_path = if let Ok(program_files) = std::env::var("ProgramFiles") {
-            std::path::PathBuf::from(program_files).join(r"Google\Chrome\Application\chrome.exe")
-        } else {
-            // 如果获取环境变量失败，默认使用C盘路径
-            std::path::PathBuf::from(r"C:\Program Files\Google\Chrome\Application\chrome.exe")
-        };
-
-        let launch_options = LaunchOptionsBuilder::default()
-            .headless(false) // 禁用无头模式，因为很多网站会阻止无头浏览器
-            .path(Some(chrome_path)) // 设置Chrome路径
-            .window_size(Some((800, 600)))
-            // 添加命令行参数，将窗口定位到屏幕外
-            .args(vec![
+        let chrome_path = find_browser_executable(self.browser.path.as_deref()).ok_or_else(|| {
+            format!(
+                "未找到Chrome/Chromium浏览器，请在配置文件的[browser]中设置path或设置{}环境变量",
+                BROWSER_PATH_ENV
+            )
+        })?;
+        println!("使用浏览器: {:?}", chrome_path);
+
+        // 默认使用非无头模式以避免403错误，并添加命令行参数，尽量隐藏浏览器窗口
+        let mut args = vec![
+            OsStr::new("--disable-extensions"),     // 禁用扩展
+            OsStr::new("--disable-popup-blocking"), // 禁用弹窗阻止
+            OsStr::new("--disable-default-apps"),   // 禁用默认应用
+        ];
+        // 浏览器同样使用配置的代理
+        let proxy_args = self.browser_proxy_args(options.bypass_proxy);
+        args.extend(proxy_args.iter().map(OsStr::new));
+        if !self.browser.headless {
+            args.extend([
                 OsStr::new("--window-position=-32000,-32000"), // 将窗口定位到屏幕外
                 OsStr::new("--no-startup-window"),             // 不显示启动窗口
                 OsStr::new("--silent-launch"),                 // 静默启动
-                OsStr::new("--disable-extensions"),            // 禁用扩展
-                OsStr::new("--disable-popup-blocking"),        // 禁用弹窗阻止
-                OsStr::new("--disable-default-apps"),          // 禁用默认应用
-            ])
+            ]);
+        }
+
+        let launch_options = LaunchOptionsBuilder::default()
+            .headless(self.browser.headless)
+            .path(Some(chrome_path)) // 设置浏览器路径
+            .window_size(Some((800, 600)))
+            .args(args)
             .build()?;
 
         // 启动浏览器
@@ -75,6 +523,20 @@ impl ReqwestFetcher {
         // 创建新标签页
         let tab = browser.new_tab()?;
 
+        // 应用RSS源的自定义User-Agent、请求头和认证信息
+        if let Some(user_agent) = &options.user_agent {
+            tab.set_user_agent(user_agent, None, None)?;
+        }
+        let extra_headers = request_headers(options, true);
+        let extra_headers: HashMap<&str, &str> = extra_headers
+            .iter()
+            .filter(|(name, _)| *name != USER_AGENT)
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)))
+            .collect();
+        if !extra_headers.is_empty() {
+            tab.set_extra_http_headers(extra_headers)?;
+        }
+
         // 导航到RSS源URL
         tab.navigate_to(url)?;
 
@@ -133,11 +595,37 @@ impl ReqwestFetcher {
         Ok(rss_content)
     }
     
+    /// 浏览器的代理参数
+    #[cfg(not(any(target_os = "android", target_os = "ios")))]
+    fn browser_proxy_args(&self, bypass_proxy: bool) -> Vec<String> {
+        if bypass_proxy {
+            return vec!["--no-proxy-server".to_string()];
+        }
+        let Some(proxy) = self
+            .network
+            .proxy
+            .as_deref()
+            .filter(|proxy| !proxy.is_empty())
+        else {
+            return Vec::new();
+        };
+        let mut args = vec![format!("--proxy-server={}", proxy)];
+        if let Some(no_proxy) = self.network.no_proxy.as_deref() {
+            // Chrome的绕过列表使用分号分隔
+            args.push(format!(
+                "--proxy-bypass-list={}",
+                no_proxy.replace(',', ";")
+            ));
+        }
+        args
+    }
+
     /// Android/iOS平台的403错误处理
     #[cfg(any(target_os = "android", target_os = "ios"))]
     async fn fetch_with_headless_chrome(
         &self,
         url: &str,
+        _options: &FeedRequestOptions,
     ) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
         // 在Android/iOS平台上，直接返回403错误，不尝试使用Headless Chrome
         // 因为Headless Chrome在移动平台上不可用
@@ -145,52 +633,324 @@ impl ReqwestFetcher {
     }
 }
 
-#[async_trait]
-impl RssFetcher for ReqwestFetcher {
-    async fn fetch(&self, url: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
-        // 首先尝试使用reqwest获取内容
-        let response = self.client.get(url).send().await?;
+/// 读取响应头的字符串值
+fn header_value(response: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+}
 
-        // 检查响应状态码
-        if response.status() == StatusCode::FORBIDDEN {
-            // 遇到403错误
-            println!("遇到403错误: {}", url);
-            
-            #[cfg(not(any(target_os = "android", target_os = "ios")))]
-            {
-                // 在非Android/iOS平台上，尝试使用Headless Chrome获取
-                println!("尝试使用Headless Chrome获取RSS源");
-                return self.fetch_with_headless_chrome(url).await;
-            }
-            
-            #[cfg(any(target_os = "android", target_os = "ios"))]
-            {
-                // 在Android/iOS平台上，直接返回错误
-                // 因为Headless Chrome在移动平台上不可用
-                return Err(format!("获取RSS源失败: 遇到403 Forbidden错误，在移动平台上不支持Headless Chrome获取 ({})", url).into());
+/// 根据RSS源的自定义请求设置生成请求头
+///
+/// include_credentials为false时只保留User-Agent，用于跨域重定向，避免把Cookie和认证信息发送给其他站点
+fn request_headers(options: &FeedRequestOptions, include_credentials: bool) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    if let Some(value) = options
+        .user_agent
+        .as_deref()
+        .and_then(|user_agent| HeaderValue::from_str(user_agent).ok())
+    {
+        headers.insert(USER_AGENT, value);
+    }
+    if !include_credentials {
+        return headers;
+    }
+
+    for (name, value) in &options.headers {
+        match (
+            HeaderName::from_bytes(name.trim().as_bytes()),
+            HeaderValue::from_str(value.trim()),
+        ) {
+            (Ok(name), Ok(mut value)) => {
+                value.set_sensitive(true);
+                headers.insert(name, value);
             }
+            _ => eprintln!("忽略无效的自定义请求头: {}", name),
+        }
+    }
+
+    let mut sensitive = |name: HeaderName, value: String| match HeaderValue::from_str(&value) {
+        Ok(mut value) => {
+            value.set_sensitive(true);
+            headers.insert(name, value);
         }
+        Err(_) => eprintln!("忽略无效的请求头值: {}", name),
+    };
+    if let Some(cookie) = &options.cookie {
+        sensitive(COOKIE, cookie.clone());
+    }
+    if let Some(token) = &options.bearer_token {
+        sensitive(AUTHORIZATION, format!("Bearer {}", token));
+    } else if let Some(username) = &options.username {
+        let credentials = format!("{}:{}", username, options.password.as_deref().unwrap_or(""));
+        let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+        sensitive(AUTHORIZATION, format!("Basic {}", encoded));
+    }
+
+    headers
+}
+
+#[async_trait]
+impl RssFetcher for ReqwestFetcher {
+    async fn fetch(&self, url: &str) -> Result<String, AppError> {
+        let response = self.fetch_conditional(url, None, None).await?;
+        Ok(response.content)
+    }
+
+    async fn fetch_conditional(
+        &self,
+        url: &str,
+        etag: Option<&str>,
+        last_modified: Option<&str>,
+    ) -> Result<FetchResponse, AppError> {
+        self.fetch_with_options(url, etag, last_modified, &FeedRequestOptions::default())
+            .await
+    }
+}
+
+/// JSON Feed文档（https://jsonfeed.org）
+#[derive(Debug, Deserialize)]
+struct JsonFeed {
+    version: String,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    items: Vec<JsonFeedItem>,
+    /// JSON Feed 1.0的作者字段
+    #[serde(default)]
+    author: Option<JsonFeedAuthor>,
+    /// JSON Feed 1.1的作者列表
+    #[serde(default)]
+    authors: Vec<JsonFeedAuthor>,
+    /// 源自身的地址
+    #[serde(default)]
+    feed_url: Option<String>,
+    /// 下一页（更早的条目）的地址
+    #[serde(default)]
+    next_url: Option<String>,
+    /// WebSub推送中心
+    #[serde(default)]
+    hubs: Vec<JsonFeedHub>,
+}
+
+/// JSON Feed中的WebSub推送中心
+#[derive(Debug, Deserialize)]
+struct JsonFeedHub {
+    url: String,
+}
+
+/// JSON Feed条目
+#[derive(Debug, Deserialize)]
+struct JsonFeedItem {
+    /// 规范要求为字符串，但部分发布者使用数字
+    #[serde(default)]
+    id: Option<serde_json::Value>,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(default)]
+    external_url: Option<String>,
+    #[serde(default)]
+    title: Option<String>,
+    #[serde(default)]
+    content_html: Option<String>,
+    #[serde(default)]
+    content_text: Option<String>,
+    #[serde(default)]
+    summary: Option<String>,
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    banner_image: Option<String>,
+    #[serde(default)]
+    date_published: Option<String>,
+    #[serde(default)]
+    date_modified: Option<String>,
+    #[serde(default)]
+    author: Option<JsonFeedAuthor>,
+    #[serde(default)]
+    authors: Vec<JsonFeedAuthor>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    attachments: Vec<JsonFeedAttachment>,
+}
+
+/// JSON Feed作者
+#[derive(Debug, Deserialize)]
+struct JsonFeedAuthor {
+    #[serde(default)]
+    name: Option<String>,
+}
+
+/// JSON Feed附件
+#[derive(Debug, Deserialize)]
+struct JsonFeedAttachment {
+    url: String,
+    #[serde(default)]
+    mime_type: String,
+    #[serde(default)]
+    size_in_bytes: Option<i64>,
+    #[serde(default)]
+    duration_in_seconds: Option<f64>,
+}
+
+/// 缩略图的最小尺寸（像素），声明的宽或高小于该值的图片通常是头像、表情或统计像素
+const MIN_THUMBNAIL_SIZE: u32 = 50;
+
+/// 不适合作为缩略图的图片地址（头像、表情等）
+const NON_THUMBNAIL_PATTERNS: &[&str] = &[
+    "gravatar.com/avatar",
+    "/avatar",
+    "s.w.org/images/core/emoji",
+    "/emoji/",
+    "/smilies/",
+];
+
+/// 扩展元素的统一访问接口，用于同时解析RSS和Atom中的Media RSS元素
+trait MediaElement: Sized {
+    fn attr(&self, name: &str) -> Option<&str>;
+    fn child_elements(&self, name: &str) -> &[Self];
+}
+
+impl MediaElement for rss::extension::Extension {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs().get(name).map(String::as_str)
+    }
+
+    fn child_elements(&self, name: &str) -> &[Self] {
+        self.children().get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+impl MediaElement for atom_syndication::extension::Extension {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs().get(name).map(String::as_str)
+    }
+
+    fn child_elements(&self, name: &str) -> &[Self] {
+        self.children().get(name).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// 获取Media RSS的指定元素，包括直接位于条目中和位于<media:group>中的元素
+fn media_elements<'a, E: MediaElement>(
+    extensions: &'a BTreeMap<String, BTreeMap<String, Vec<E>>>,
+    name: &str,
+) -> Vec<&'a E> {
+    let Some(media) = extensions.get("media") else {
+        return Vec::new();
+    };
+    let direct = media.get(name).into_iter().flatten();
+    let grouped = media
+        .get("group")
+        .into_iter()
+        .flatten()
+        .flat_map(|group| group.child_elements(name));
+    direct.chain(grouped).collect()
+}
+
+/// 将<media:content>转换为媒体附件，图片返回None
+fn media_content_enclosure<E: MediaElement>(content: &E) -> Option<Enclosure> {
+    if content.attr("medium") == Some("image") {
+        return None;
+    }
+    Some(Enclosure {
+        url: content.attr("url")?.to_string(),
+        mime_type: content.attr("type").map(|t| t.to_string()),
+        length: content.attr("fileSize").and_then(|v| v.trim().parse().ok()),
+        duration: content.attr("duration").and_then(parse_duration),
+        ..Default::default()
+    })
+}
+
+/// 获取Media RSS中声明的缩略图，有多个时选择尺寸最大的
+///
+/// 候选包括<media:thumbnail>（包括<media:group>和<media:content>中的）和图片类型的<media:content>
+fn media_thumbnail<E: MediaElement>(
+    extensions: &BTreeMap<String, BTreeMap<String, Vec<E>>>,
+) -> Option<&str> {
+    let contents = media_elements(extensions, "content");
+    let nested = contents
+        .iter()
+        .flat_map(|content| content.child_elements("thumbnail"));
+    let images = contents.iter().copied().filter(|content| {
+        content.attr("medium") == Some("image")
+            || content
+                .attr("type")
+                .is_some_and(|mime_type| mime_type.starts_with("image/"))
+    });
+
+    media_elements(extensions, "thumbnail")
+        .into_iter()
+        .chain(nested)
+        .chain(images)
+        .filter_map(|element| {
+            let url = element.attr("url")?;
+            let width = parse_image_size(element.attr("width"));
+            let height = parse_image_size(element.attr("height"));
+            let area = u64::from(width.unwrap_or(0)) * u64::from(height.unwrap_or(0));
+            is_thumbnail_candidate(url, width, height).then_some((url, area))
+        })
+        // 尺寸相同时保留先出现的
+        .min_by_key(|(_, area)| Reverse(*area))
+        .map(|(url, _)| url)
+}
+
+/// 解析图片的width/height属性，百分比等无法解析的值返回None
+fn parse_image_size(value: Option<&str>) -> Option<u32> {
+    value?.trim().trim_end_matches("px").parse().ok()
+}
+
+/// 判断图片能否作为缩略图：声明的尺寸不能过小，也不能是统计像素、头像或表情
+fn is_thumbnail_candidate(url: &str, width: Option<u32>, height: Option<u32>) -> bool {
+    let too_small = [width, height]
+        .into_iter()
+        .flatten()
+        .any(|size| size < MIN_THUMBNAIL_SIZE);
+    !too_small
+        && !is_tracker_url(url)
+        && !NON_THUMBNAIL_PATTERNS
+            .iter()
+            .any(|pattern| url.contains(pattern))
+}
 
-        // 正常响应，返回内容
-        let content = response.text().await?;
-        Ok(content)
+/// 解析媒体时长，支持"HH:MM:SS"、"MM:SS"和秒数
+fn parse_duration(value: &str) -> Option<i64> {
+    let parts: Vec<&str> = value.trim().split(':').collect();
+    if parts.len() > 3 {
+        return None;
     }
+    let mut seconds = 0.0;
+    for part in parts {
+        seconds = seconds * 60.0 + part.trim().parse::<f64>().ok()?;
+    }
+    (seconds >= 0.0).then_some(seconds as i64)
 }
 
 /// RSS解析器
-pub struct RssParser;
+#[derive(Clone)]
+pub struct RssParser {
+    /// 文章链接的规范化规则
+    link_rules: LinkRules,
+}
 
 impl RssParser {
     pub fn new() -> Self {
-        Self
+        Self {
+            link_rules: LinkRules::default(),
+        }
     }
 
-    /// 解析RSS或Atom内容
-    pub fn parse(
-        &self,
-        content: &str,
-        base_url: &str,
-    ) -> Result<Vec<Article>, Box<dyn std::error::Error + Send + Sync>> {
+    /// 设置文章链接的规范化规则
+    pub fn with_link_rules(mut self, link_rules: LinkRules) -> Self {
+        self.link_rules = link_rules;
+        self
+    }
+
+    /// 解析RSS、Atom或JSON Feed内容
+    pub fn parse(&self, content: &str, base_url: &str) -> Result<Vec<Article>, AppError> {
         // 尝试解析为RSS格式
         if let Ok(channel) = Channel::read_from(content.as_bytes()) {
             // 转换RSS项为文章
@@ -211,21 +971,183 @@ impl RssParser {
                 .collect();
             Ok(articles)
         }
+        // 尝试解析为JSON Feed格式
+        else if let Some(json_feed) = Self::read_json_feed(content) {
+            // 转换JSON Feed条目为文章
+            let articles: Vec<Article> = json_feed
+                .items
+                .iter()
+                .map(|item| self.json_item_to_article(item, &json_feed, 0, base_url))
+                .collect();
+            Ok(articles)
+        }
         // 解析失败
         else {
-            Err("Failed to parse feed: not a valid RSS or Atom format".into())
+            Err(AppError::Parse(
+                "Failed to parse feed: not a valid RSS, Atom or JSON Feed format".to_string(),
+            ))
+        }
+    }
+
+    /// 识别内容的源格式，返回格式名称（rss/atom/json）和源标题
+    pub fn detect_format(&self, content: &str) -> Option<(&'static str, Option<String>)> {
+        if let Ok(channel) = Channel::read_from(content.as_bytes()) {
+            Some(("rss", Some(channel.title().to_string())))
+        } else if let Ok(atom_feed) = AtomFeed::read_from(content.as_bytes()) {
+            Some(("atom", Some(atom_feed.title().to_string())))
+        } else {
+            Self::read_json_feed(content).map(|json_feed| ("json", json_feed.title))
+        }
+    }
+
+    /// 提取源中的<link>（rel和href）以及生成器名称
+    ///
+    /// RSS中的<atom:link>保存在扩展中，JSON Feed的next_url视为rel="next"、hubs视为rel="hub"
+    fn feed_links(&self, content: &str) -> (Vec<(String, String)>, Option<String>) {
+        if let Ok(channel) = Channel::read_from(content.as_bytes()) {
+            let links = channel
+                .extensions()
+                .values()
+                .flat_map(|elements| elements.get("link").into_iter().flatten())
+                .filter_map(|link| {
+                    Some((
+                        link.attr("rel")?.to_string(),
+                        link.attr("href")?.to_string(),
+                    ))
+                })
+                .collect();
+            (links, channel.generator().map(|g| g.to_string()))
+        } else if let Ok(atom_feed) = AtomFeed::read_from(content.as_bytes()) {
+            let links = atom_feed
+                .links()
+                .iter()
+                .map(|link| (link.rel().to_string(), link.href().to_string()))
+                .collect();
+            let generator = atom_feed
+                .generator()
+                .map(|g| format!("{} {}", g.value(), g.uri().unwrap_or_default()));
+            (links, generator)
+        } else if let Some(json_feed) = Self::read_json_feed(content) {
+            let next = json_feed.next_url.map(|url| ("next".to_string(), url));
+            let hubs = json_feed
+                .hubs
+                .into_iter()
+                .map(|hub| ("hub".to_string(), hub.url));
+            let feed_url = json_feed.feed_url.map(|url| ("self".to_string(), url));
+            (next.into_iter().chain(hubs).chain(feed_url).collect(), None)
+        } else {
+            (Vec::new(), None)
+        }
+    }
+
+    /// 查找指定rel的链接，相对地址按base_url解析为绝对地址
+    fn find_link(links: &[(String, String)], rel: &str, base_url: &str) -> Option<String> {
+        let (_, href) = links
+            .iter()
+            .find(|(link_rel, _)| link_rel.eq_ignore_ascii_case(rel))?;
+        let href = href.trim();
+        url::Url::parse(base_url)
+            .and_then(|base_url| base_url.join(href))
+            .or_else(|_| url::Url::parse(href))
+            .ok()
+            .map(|url| url.to_string())
+    }
+
+    /// 提取源中的分页信息：RFC 5005的分页/存档链接、JSON Feed的next_url以及是否由WordPress生成
+    pub fn paging_links(&self, content: &str, base_url: &str) -> PagingLinks {
+        let (links, generator) = self.feed_links(content);
+        PagingLinks {
+            // 分页源的rel="next"和存档源的rel="prev-archive"都指向更早的文章
+            next: Self::find_link(&links, "next", base_url)
+                .or_else(|| Self::find_link(&links, "prev-archive", base_url)),
+            wordpress: generator.is_some_and(|g| g.to_lowercase().contains("wordpress")),
+        }
+    }
+
+    /// 提取源声明的WebSub推送中心（rel="hub"）和主题地址（rel="self"，未声明时使用源地址）
+    pub fn websub_links(&self, content: &str, base_url: &str) -> Option<WebSubLinks> {
+        let (links, _) = self.feed_links(content);
+        Some(WebSubLinks {
+            hub: Self::find_link(&links, "hub", base_url)?,
+            topic: Self::find_link(&links, "self", base_url)
+                .unwrap_or_else(|| base_url.to_string()),
+        })
+    }
+
+    /// 提取RSS源中的调度提示（<ttl>、<skipHours>、<skipDays>和sy:updatePeriod）
+    pub fn schedule_hints(&self, content: &str) -> ScheduleHints {
+        let channel = match Channel::read_from(content.as_bytes()) {
+            Ok(channel) => channel,
+            Err(_) => return ScheduleHints::default(),
+        };
+
+        // <ttl>以分钟为单位
+        let ttl = channel
+            .ttl()
+            .and_then(|ttl| ttl.trim().parse::<i64>().ok())
+            .filter(|minutes| *minutes > 0)
+            .map(chrono::Duration::minutes);
+
+        // sy:updatePeriod / sy:updateFrequency表示每个周期更新的次数
+        let syndication = channel.syndication_ext().map(|sy| {
+            let period = match sy.period() {
+                UpdatePeriod::Hourly => chrono::Duration::hours(1),
+                UpdatePeriod::Daily => chrono::Duration::days(1),
+                UpdatePeriod::Weekly => chrono::Duration::weeks(1),
+                UpdatePeriod::Monthly => chrono::Duration::days(30),
+                UpdatePeriod::Yearly => chrono::Duration::days(365),
+            };
+            period / sy.frequency().max(1) as i32
+        });
+
+        // 同时存在时取较长的间隔
+        let min_interval = match (ttl, syndication) {
+            (Some(a), Some(b)) => Some(a.max(b)),
+            (a, b) => a.or(b),
+        };
+
+        let skip_hours = channel
+            .skip_hours()
+            .iter()
+            .filter_map(|hour| hour.trim().parse::<u32>().ok())
+            .filter(|hour| *hour < 24)
+            .collect();
+        let skip_days = channel
+            .skip_days()
+            .iter()
+            .filter_map(|day| day.trim().parse::<Weekday>().ok())
+            .collect();
+
+        ScheduleHints {
+            min_interval,
+            skip_hours,
+            skip_days,
         }
     }
 
-    /// 标准化链接，去除#fragment部分
-    fn normalize_link(&self, link: &str) -> String {
-        if let Some(hash_pos) = link.find('#') {
-            link[..hash_pos].to_string()
+    /// 尝试将内容解析为JSON Feed，版本字段必须指向jsonfeed.org
+    fn read_json_feed(content: &str) -> Option<JsonFeed> {
+        let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+        if !trimmed.starts_with('{') {
+            return None;
+        }
+
+        let json_feed: JsonFeed = serde_json::from_str(trimmed).ok()?;
+        if json_feed
+            .version
+            .starts_with("https://jsonfeed.org/version/")
+        {
+            Some(json_feed)
         } else {
-            link.to_string()
+            None
         }
     }
 
+    /// 标准化链接：按源地址解析相对链接，去除#fragment和跟踪参数
+    fn normalize_link(&self, link: &str, base_url: &str) -> String {
+        self.link_rules.canonicalize(link, Some(base_url))
+    }
+
     /// 检查文章标题和内容是否包含黑名单关键字
     pub fn check_blacklist(
         &self,
@@ -249,26 +1171,17 @@ impl RssParser {
 
     /// 将RSS项转换为文章模型
     pub fn rss_item_to_article(&self, item: &Item, feed_id: i64, base_url: &str) -> Article {
+        // 源中没有日期或日期无法解析时，使用首次获取到文章的时间
+        let first_seen = Utc::now();
         let pub_date = item
             .pub_date()
-            .and_then(|d| {
-                chrono::DateTime::parse_from_rfc2822(d)
-                    .ok()
-                    .map(|dt| dt.into())
-            })
+            .and_then(parse_date)
             .or_else(|| {
-                if let Some(dc) = item.dublin_core_ext() {
-                    let dates = dc.dates();
-                    if let Some(date_str) = dates.first() {
-                        return DateTime::parse_from_rfc3339(date_str)
-                            .or_else(|_| DateTime::parse_from_str(date_str, "%Y-%m-%dT%H:%M:%S%z"))
-                            .ok()
-                            .map(|dt| dt.into());
-                    }
-                }
-                None
+                item.dublin_core_ext()
+                    .and_then(|dc| dc.dates().first())
+                    .and_then(|date| parse_date(date))
             })
-            .unwrap_or_else(Utc::now);
+            .unwrap_or(first_seen);
 
         let categories = item
             .categories()
@@ -282,8 +1195,11 @@ impl RssParser {
             .unwrap_or_else(|| item.description().unwrap_or(""))
             .to_string();
 
-        // 修复内容中的图片URL
-        let content = self.fix_content_images(&content, base_url);
+        // 在清理和修复链接之前计算原始内容的哈希，用于检测发布者修改
+        let source_hash = content_hash(item.title().unwrap_or(""), &content);
+
+        // 修复内容中的图片URL，并清理脚本、事件处理属性和统计像素
+        let content = sanitize_html(&self.fix_content_images(&content, base_url));
 
         let thumbnail = item
             .enclosure()
@@ -296,13 +1212,24 @@ impl RssParser {
                     None
                 }
             })
+            .or_else(|| {
+                // Media RSS中声明的缩略图
+                media_thumbnail(item.extensions())
+                    .and_then(|url| self.fix_image_url(url, base_url))
+            })
             .or_else(|| {
                 // 尝试从内容中提取第一张图片
                 self.extract_first_image(&content, base_url)
+            })
+            .or_else(|| {
+                // 播客节目使用iTunes扩展中的封面
+                item.itunes_ext()
+                    .and_then(|itunes| itunes.image())
+                    .and_then(|url| self.fix_image_url(url, base_url))
             });
 
         let original_link = item.link().unwrap_or("");
-        let normalized_link = self.normalize_link(original_link);
+        let normalized_link = self.normalize_link(original_link, base_url);
 
         // 这里暂时不应用黑名单过滤，因为需要从数据库获取黑名单关键字
         // 黑名单过滤将在lib.rs中处理
@@ -322,16 +1249,27 @@ impl RssParser {
             categories,
             translated_title: None,   // 默认无翻译标题
             translated_content: None, // 默认无翻译内容
+            summary: None,
+            guid: item.guid().map(|guid| guid.value().to_string()),
+            updated: None,
+            first_seen: Some(first_seen),
+            enclosures: self.rss_enclosures(item, base_url),
+            source_hash: Some(source_hash),
         }
     }
 
     /// 将Atom条目转换为文章模型
     pub fn atom_item_to_article(&self, entry: &AtomEntry, feed_id: i64, base_url: &str) -> Article {
-        // 处理发布日期，确保转换为Utc时间
+        // 源中没有日期时，使用首次获取到文章的时间
+        // 缺少updated元素时atom_syndication会填入Unix纪元时间，视为没有日期
+        let first_seen = Utc::now();
+        let updated =
+            Some(entry.updated().with_timezone(&Utc)).filter(|updated| updated.timestamp() != 0);
         let pub_date: DateTime<Utc> = entry
             .published()
             .map(|dt| dt.with_timezone(&Utc))
-            .unwrap_or_else(|| entry.updated().with_timezone(&Utc));
+            .or(updated)
+            .unwrap_or(first_seen);
 
         let categories = entry
             .categories()
@@ -349,7 +1287,12 @@ impl RssParser {
             })
             .and_then(|link| self.fix_image_url(link.href(), base_url))
             .or_else(|| {
-                // 尝试从内容中提取第一张图片
+                // Media RSS中声明的缩略图
+                media_thumbnail(entry.extensions())
+                    .and_then(|url| self.fix_image_url(url, base_url))
+            })
+            .or_else(|| {
+                // 尝试从内容中提取第一张图片
                 let content = entry
                     .content()
                     .and_then(|c| c.value())
@@ -365,9 +1308,10 @@ impl RssParser {
             .or_else(|| entry.summary().map(|s| &**s))
             .unwrap_or_default()
             .to_string();
+        let source_hash = content_hash(entry.title(), &content);
 
-        // 修复内容中的图片URL
-        let content = self.fix_content_images(&content, base_url);
+        // 修复内容中的图片URL，并清理脚本、事件处理属性和统计像素
+        let content = sanitize_html(&self.fix_content_images(&content, base_url));
 
         // 处理链接
         let original_link = entry
@@ -376,7 +1320,7 @@ impl RssParser {
             .find(|link| link.rel() == "alternate")
             .map(|link| link.href().to_string())
             .unwrap_or_default();
-        let normalized_link = self.normalize_link(&original_link);
+        let normalized_link = self.normalize_link(&original_link, base_url);
 
         // 处理作者
         let author = entry
@@ -403,15 +1347,224 @@ impl RssParser {
             categories,
             translated_title: None,   // 默认无翻译标题
             translated_content: None, // 默认无翻译内容
+            summary: None,
+            guid: Some(entry.id().to_string()),
+            updated,
+            first_seen: Some(first_seen),
+            enclosures: self.atom_enclosures(entry, base_url),
+            source_hash: Some(source_hash),
         }
     }
 
-    /// 从HTML内容中提取第一张图片，并确保URL是完整的绝对路径
+    /// 将JSON Feed条目转换为文章模型
+    fn json_item_to_article(
+        &self,
+        item: &JsonFeedItem,
+        feed: &JsonFeed,
+        feed_id: i64,
+        base_url: &str,
+    ) -> Article {
+        // 源中没有日期或日期无法解析时，使用首次获取到文章的时间
+        let first_seen = Utc::now();
+        let pub_date = item
+            .date_published
+            .as_deref()
+            .or(item.date_modified.as_deref())
+            .and_then(parse_date)
+            .unwrap_or(first_seen);
+
+        // 获取文章内容，优先使用content_html，其次使用content_text和summary
+        let content = match (&item.content_html, &item.content_text) {
+            (Some(html), _) => html.clone(),
+            (None, Some(text)) => html_escape::encode_text(text).replace('\n', "<br>"),
+            (None, None) => item.summary.clone().unwrap_or_default(),
+        };
+        let source_hash = content_hash(item.title.as_deref().unwrap_or(""), &content);
+
+        // 修复内容中的图片URL，并清理脚本、事件处理属性和统计像素
+        let content = sanitize_html(&self.fix_content_images(&content, base_url));
+
+        // 处理缩略图，依次使用image、banner_image、图片附件和内容中的第一张图片
+        let thumbnail = item
+            .image
+            .as_deref()
+            .or(item.banner_image.as_deref())
+            .or_else(|| {
+                item.attachments
+                    .iter()
+                    .find(|a| a.mime_type.starts_with("image/"))
+                    .map(|a| a.url.as_str())
+            })
+            .and_then(|url| self.fix_image_url(url, base_url))
+            .or_else(|| self.extract_first_image(&content, base_url));
+
+        // 处理链接，url为空时使用external_url，再退回到id
+        let original_link = item
+            .url
+            .as_deref()
+            .or(item.external_url.as_deref())
+            .map(|link| link.to_string())
+            .or_else(|| match &item.id {
+                Some(serde_json::Value::String(id)) if id.starts_with("http") => Some(id.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+        let normalized_link = self.normalize_link(&original_link, base_url);
+
+        // 处理作者，条目作者优先于源作者，同时兼容1.0和1.1的字段
+        let author = item
+            .authors
+            .first()
+            .or(item.author.as_ref())
+            .or(feed.authors.first())
+            .or(feed.author.as_ref())
+            .and_then(|author| author.name.clone());
+
+        // 这里暂时不应用黑名单过滤，因为需要从数据库获取黑名单关键字
+        // 黑名单过滤将在lib.rs中处理
+        let title = item
+            .title
+            .clone()
+            .filter(|t| !t.is_empty())
+            .unwrap_or_else(|| "无标题".to_string());
+
+        Article {
+            id: 0, // 数据库将自动生成
+            feed_id,
+            title,
+            content,
+            pub_date,
+            link: normalized_link,
+            is_read: false,
+            is_favorite: false,
+            thumbnail,
+            author,
+            categories: item.tags.clone(),
+            translated_title: None,   // 默认无翻译标题
+            translated_content: None, // 默认无翻译内容
+            summary: None,
+            guid: item.id.as_ref().and_then(|id| match id {
+                serde_json::Value::String(id) => Some(id.clone()),
+                serde_json::Value::Number(id) => Some(id.to_string()),
+                _ => None,
+            }),
+            updated: item.date_modified.as_deref().and_then(parse_date),
+            first_seen: Some(first_seen),
+            enclosures: self.normalize_enclosures(
+                item.attachments
+                    .iter()
+                    .map(|attachment| Enclosure {
+                        url: attachment.url.clone(),
+                        mime_type: Some(attachment.mime_type.clone())
+                            .filter(|mime_type| !mime_type.is_empty()),
+                        length: attachment.size_in_bytes,
+                        duration: attachment.duration_in_seconds.map(|d| d as i64),
+                        ..Default::default()
+                    })
+                    .collect(),
+                base_url,
+            ),
+            source_hash: Some(source_hash),
+        }
+    }
+
+    /// 提取RSS项的媒体附件：<enclosure>、Media RSS的<media:content>，并补充iTunes扩展中的节目信息
+    fn rss_enclosures(&self, item: &Item, base_url: &str) -> Vec<Enclosure> {
+        let mut enclosures: Vec<Enclosure> = item
+            .enclosure()
+            .map(|enclosure| Enclosure {
+                url: enclosure.url().to_string(),
+                mime_type: Some(enclosure.mime_type().to_string())
+                    .filter(|mime_type| !mime_type.is_empty()),
+                length: enclosure.length().trim().parse().ok(),
+                ..Default::default()
+            })
+            .into_iter()
+            .collect();
+        enclosures.extend(
+            media_elements(item.extensions(), "content")
+                .into_iter()
+                .filter_map(media_content_enclosure),
+        );
+        let mut enclosures = self.normalize_enclosures(enclosures, base_url);
+
+        if let Some(itunes) = item.itunes_ext() {
+            let image = itunes
+                .image()
+                .and_then(|url| self.fix_image_url(url, base_url));
+            for enclosure in &mut enclosures {
+                enclosure.duration = enclosure
+                    .duration
+                    .or_else(|| itunes.duration().and_then(parse_duration));
+                enclosure.image = image.clone();
+                enclosure.episode = itunes.episode().and_then(|e| e.trim().parse().ok());
+                enclosure.season = itunes.season().and_then(|s| s.trim().parse().ok());
+            }
+        }
+        enclosures
+    }
+
+    /// 提取Atom条目的媒体附件：rel="enclosure"的链接和Media RSS的<media:content>
+    fn atom_enclosures(&self, entry: &AtomEntry, base_url: &str) -> Vec<Enclosure> {
+        let mut enclosures: Vec<Enclosure> = entry
+            .links()
+            .iter()
+            .filter(|link| link.rel() == "enclosure")
+            .map(|link| Enclosure {
+                url: link.href().to_string(),
+                mime_type: link.mime_type().map(|mime_type| mime_type.to_string()),
+                length: link.length().and_then(|length| length.trim().parse().ok()),
+                ..Default::default()
+            })
+            .collect();
+        enclosures.extend(
+            media_elements(entry.extensions(), "content")
+                .into_iter()
+                .filter_map(media_content_enclosure),
+        );
+        self.normalize_enclosures(enclosures, base_url)
+    }
+
+    /// 修复附件地址，去掉图片附件（图片只用作缩略图）、重复的附件以及非http(s)地址的附件
+    ///
+    /// 附件地址会交给播放器和外部浏览器打开，javascript:、file:、data:等地址一律丢弃
+    fn normalize_enclosures(&self, enclosures: Vec<Enclosure>, base_url: &str) -> Vec<Enclosure> {
+        let mut result: Vec<Enclosure> = Vec::new();
+        for mut enclosure in enclosures {
+            if enclosure
+                .mime_type
+                .as_deref()
+                .is_some_and(|mime_type| mime_type.starts_with("image/"))
+            {
+                continue;
+            }
+            let Some(url) = self
+                .fix_image_url(&enclosure.url, base_url)
+                .filter(|url| is_http_url(url))
+            else {
+                continue;
+            };
+            if result.iter().any(|existing| existing.url == url) {
+                continue;
+            }
+            enclosure.url = url;
+            result.push(enclosure);
+        }
+        result
+    }
+
+    /// 从HTML内容中提取第一张可用作缩略图的图片，并确保URL是完整的绝对路径
+    ///
+    /// 跳过声明尺寸过小的图片以及统计像素、头像和表情
     fn extract_first_image(&self, content: &str, base_url: &str) -> Option<String> {
         let document = scraper::Html::parse_document(content);
         let img_selector = scraper::Selector::parse("img").ok()?;
-        let img_element = document.select(&img_selector).next()?;
-        let src = img_element.value().attr("src")?;
+        let src = document.select(&img_selector).find_map(|img| {
+            let src = img.value().attr("src")?;
+            let width = parse_image_size(img.value().attr("width"));
+            let height = parse_image_size(img.value().attr("height"));
+            is_thumbnail_candidate(src, width, height).then_some(src)
+        })?;
 
         // 处理图片URL，确保是完整的绝对路径
         self.fix_image_url(src, base_url)
@@ -507,42 +1660,236 @@ impl RssParser {
     }
 }
 
+/// 源中的分页信息，用于回溯历史文章
+#[derive(Debug, Clone, Default)]
+pub struct PagingLinks {
+    /// 下一页（更早的文章）的地址
+    pub next: Option<String>,
+    /// 源由WordPress生成，可以通过?paged=N获取更早的文章
+    pub wordpress: bool,
+}
+
+/// 源声明的WebSub推送信息
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebSubLinks {
+    /// 推送中心地址
+    pub hub: String,
+    /// 订阅的主题，即源的规范地址
+    pub topic: String,
+}
+
+/// 是否可以跟随源中的分页链接
+///
+/// 分页链接由源内容提供，网络源只允许http(s)链接，避免源通过file:或exec:链接读取本地文件或运行本地命令。
+/// 本地文件和本地命令源只获取用户填写的源地址，不跟随分页链接
+fn is_allowed_page_url(feed_url: &str, page_url: &str) -> bool {
+    is_http_url(feed_url) && is_http_url(page_url)
+}
+
+/// 是否为http(s)地址
+fn is_http_url(url: &str) -> bool {
+    url::Url::parse(url)
+        .map(|url| matches!(url.scheme(), "http" | "https"))
+        .unwrap_or(false)
+}
+
+/// 生成WordPress源第page页的地址
+fn wordpress_page_url(feed_url: &str, page: usize) -> Option<String> {
+    let mut url = url::Url::parse(feed_url).ok()?;
+    let pairs: Vec<(String, String)> = url
+        .query_pairs()
+        .filter(|(name, _)| name != "paged")
+        .map(|(name, value)| (name.into_owned(), value.into_owned()))
+        .collect();
+    url.query_pairs_mut()
+        .clear()
+        .extend_pairs(pairs)
+        .append_pair("paged", &page.to_string());
+    Some(url.to_string())
+}
+
+/// RSS源发布者给出的调度提示
+#[derive(Debug, Clone, Default)]
+pub struct ScheduleHints {
+    /// 两次拉取之间的最小间隔
+    pub min_interval: Option<chrono::Duration>,
+    /// 无需拉取的小时（GMT，0-23）
+    pub skip_hours: Vec<u32>,
+    /// 无需拉取的星期
+    pub skip_days: Vec<Weekday>,
+}
+
+impl ScheduleHints {
+    /// 提示的最小间隔上限，避免源长期不更新
+    const MAX_INTERVAL_HOURS: i64 = 24;
+
+    /// 根据调度提示计算最早的下次拉取时间，无需推迟时返回None
+    pub fn next_update_time(&self, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let max_interval = chrono::Duration::hours(Self::MAX_INTERVAL_HOURS);
+        let mut next = now
+            + self
+                .min_interval
+                .unwrap_or_else(chrono::Duration::zero)
+                .min(max_interval);
+
+        // 跳过skipHours/skipDays，按整点向后推进，最多推进一周
+        for _ in 0..24 * 7 {
+            if !self.skip_hours.contains(&next.hour()) && !self.skip_days.contains(&next.weekday())
+            {
+                break;
+            }
+            let hour_start = next.timestamp() - next.timestamp().rem_euclid(3600);
+            next = Utc.timestamp_opt(hour_start + 3600, 0).single()?;
+        }
+
+        if next > now { Some(next) } else { None }
+    }
+}
+
+/// 单个RSS源的更新结果
+#[derive(Debug, Clone, Default)]
+pub struct FeedUpdate {
+    /// 解析得到的文章
+    pub articles: Vec<Article>,
+    /// 服务器返回304，源内容未修改
+    pub not_modified: bool,
+    /// 下次条件请求使用的ETag
+    pub etag: Option<String>,
+    /// 下次条件请求使用的Last-Modified
+    pub last_modified: Option<String>,
+    /// 根据源的调度提示计算的最早下次拉取时间
+    pub next_update_time: Option<DateTime<Utc>>,
+    /// 源已永久迁移到的新地址
+    pub permanent_redirect: Option<String>,
+    /// 源声明的WebSub推送中心
+    pub websub: Option<WebSubLinks>,
+}
+
+/// 并发更新限制
+#[derive(Debug, Clone)]
+pub struct UpdateLimits {
+    /// 同时更新的RSS源数量上限
+    pub max_concurrency: usize,
+    /// 同一主机同时进行的请求数量上限
+    pub per_host_concurrency: usize,
+    /// 同一主机两次请求之间的最小间隔
+    pub per_host_delay: Duration,
+}
+
+impl Default for UpdateLimits {
+    fn default() -> Self {
+        Self {
+            max_concurrency: 8,
+            per_host_concurrency: 2,
+            per_host_delay: Duration::from_millis(500),
+        }
+    }
+}
+
+/// 回溯历史文章的限制
+#[derive(Debug, Clone)]
+pub struct BackfillOptions {
+    /// 最多获取的页数（包括源地址本身）
+    pub max_pages: usize,
+    /// 最多获取的文章数
+    pub max_articles: usize,
+}
+
+impl Default for BackfillOptions {
+    fn default() -> Self {
+        Self {
+            max_pages: 50,
+            max_articles: 1000,
+        }
+    }
+}
+
+/// 单个主机的请求限制状态
+struct HostLimiter {
+    permits: Semaphore,
+    last_request: Mutex<Option<Instant>>,
+}
+
 /// RSS更新器
 pub struct RssUpdater {
     reqwest_fetcher: ReqwestFetcher,
+    file_fetcher: FileFetcher,
+    exec_fetcher: ExecFetcher,
     parser: RssParser,
+    limits: UpdateLimits,
 }
 
 impl RssUpdater {
     pub fn new() -> Self {
         Self {
             reqwest_fetcher: ReqwestFetcher::new(),
+            file_fetcher: FileFetcher,
+            exec_fetcher: ExecFetcher::default(),
             parser: RssParser::new(),
+            limits: UpdateLimits::default(),
+        }
+    }
+
+    /// 设置并发更新限制（返回新的实例）
+    pub fn with_limits(mut self, limits: UpdateLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// 设置浏览器回退选项（返回新的实例）
+    pub fn with_browser(mut self, browser: BrowserOptions) -> Self {
+        self.reqwest_fetcher.browser = browser;
+        self
+    }
+
+    /// 设置代理和CA证书
+    pub fn with_network(mut self, network: NetworkOptions) -> Self {
+        self.reqwest_fetcher = self.reqwest_fetcher.with_network(network);
+        self
+    }
+
+    /// 设置文章链接的规范化规则
+    pub fn with_link_rules(mut self, link_rules: LinkRules) -> Self {
+        self.parser = self.parser.with_link_rules(link_rules);
+        self
+    }
+
+    /// 设置本地命令源可以运行的命令
+    pub fn with_exec(mut self, exec_fetcher: ExecFetcher) -> Self {
+        self.exec_fetcher = exec_fetcher;
+        self
+    }
+
+    /// 本地文件（file://）和本地命令（exec:）源使用的获取器，网络源返回None
+    fn local_fetcher(&self, url: &str) -> Option<&dyn RssFetcher> {
+        if is_file_url(url) {
+            Some(&self.file_fetcher)
+        } else if is_exec_url(url) {
+            Some(&self.exec_fetcher)
+        } else {
+            None
         }
     }
 
     /// 更新单个RSS源，支持智能重试
-    pub async fn update_feed(
-        &self,
-        feed: &Feed,
-    ) -> Result<Vec<Article>, Box<dyn std::error::Error + Send + Sync>> {
+    pub async fn update_feed(&self, feed: &Feed) -> Result<FeedUpdate, AppError> {
         const MAX_RETRIES: u32 = 2;
         const RETRY_DELAY: tokio::time::Duration = tokio::time::Duration::from_secs(1);
 
-        let mut last_error: Option<Box<dyn std::error::Error + Send + Sync>> = None;
+        let mut last_error: Option<AppError> = None;
 
         println!("开始更新RSS源: {} ({})", feed.name, feed.url);
 
         // 尝试获取RSS内容，支持重试
         for attempt in 0..=MAX_RETRIES {
             match self.attempt_update_feed(feed).await {
-                Ok(articles) => {
+                Ok(update) => {
                     println!(
                         "成功更新RSS源: {}，获取到 {} 篇文章",
                         feed.name,
-                        articles.len()
+                        update.articles.len()
                     );
-                    return Ok(articles);
+                    return Ok(update);
                 }
                 Err(e) => {
                     // 详细的错误日志
@@ -553,15 +1900,20 @@ impl RssUpdater {
                         MAX_RETRIES + 1
                     );
                     eprintln!("  URL: {}", feed.url);
-                    eprintln!(
-                        "  错误类型: {}",
-                        std::any::type_name::<dyn std::error::Error + Send + Sync>()
-                    );
+                    eprintln!("  错误类型: {}", e.kind());
                     eprintln!("  错误信息: {}", e);
 
+                    // 服务器要求稍后重试或明确返回错误状态码时不再立即重试
+                    let retry_later =
+                        matches!(e, AppError::RateLimited { .. } | AppError::Http { .. });
+
                     // 记录错误
                     last_error = Some(e);
 
+                    if retry_later {
+                        break;
+                    }
+
                     // 如果不是最后一次尝试，等待一段时间后重试
                     if attempt < MAX_RETRIES {
                         eprintln!("  {}秒后重试...", RETRY_DELAY.as_secs());
@@ -578,18 +1930,53 @@ impl RssUpdater {
     }
 
     /// 单次尝试更新RSS源
-    async fn attempt_update_feed(
-        &self,
-        feed: &Feed,
-    ) -> Result<Vec<Article>, Box<dyn std::error::Error + Send + Sync>> {
-        // 使用reqwest获取内容
+    async fn attempt_update_feed(&self, feed: &Feed) -> Result<FeedUpdate, AppError> {
+        // 使用reqwest获取内容，带上上次保存的缓存验证头
         println!("  正在获取RSS内容: {}", feed.url);
-        let content = self.reqwest_fetcher.fetch(&feed.url).await?;
-        println!("  成功获取RSS内容，大小: {} 字节", content.len());
+        let response = if let Some(fetcher) = self.local_fetcher(&feed.url) {
+            // 本地文件和命令源，同样使用上次保存的修改时间判断是否需要解析
+            fetcher
+                .fetch_conditional(
+                    &feed.url,
+                    feed.etag.as_deref(),
+                    feed.last_modified.as_deref(),
+                )
+                .await?
+        } else if feed.always_use_browser {
+            // 已知会拦截普通请求的源，直接使用浏览器获取
+            println!("  该RSS源设置为始终使用浏览器获取");
+            self.reqwest_fetcher
+                .fetch_with_browser(&feed.url, &feed.request_options)
+                .await?
+        } else {
+            self.reqwest_fetcher
+                .fetch_with_options(
+                    &feed.url,
+                    feed.etag.as_deref(),
+                    feed.last_modified.as_deref(),
+                    &feed.request_options,
+                )
+                .await?
+        };
+
+        // 内容未修改，无需解析
+        if response.not_modified {
+            println!("  RSS内容未修改，跳过解析");
+            return Ok(FeedUpdate {
+                articles: Vec::new(),
+                not_modified: true,
+                etag: response.etag,
+                last_modified: response.last_modified,
+                permanent_redirect: response.permanent_redirect,
+                ..Default::default()
+            });
+        }
+        println!("  成功获取RSS内容，大小: {} 字节", response.content.len());
 
-        // 解析RSS或Atom内容，传递feed.url作为base_url
+        // 解析RSS或Atom内容，传递源地址作为base_url（已永久迁移时使用新地址）
         println!("  正在解析RSS内容...");
-        let mut articles = self.parser.parse(&content, &feed.url)?;
+        let base_url = response.permanent_redirect.as_deref().unwrap_or(&feed.url);
+        let mut articles = self.parser.parse(&response.content, base_url)?;
         println!("  成功解析RSS内容，找到 {} 篇文章", articles.len());
 
         // 设置feed_id
@@ -597,23 +1984,318 @@ impl RssUpdater {
             article.feed_id = feed.id;
         }
 
+        // 根据源的TTL等调度提示计算下次拉取时间
+        let next_update_time = self
+            .parser
+            .schedule_hints(&response.content)
+            .next_update_time(Utc::now());
+        let websub = self.parser.websub_links(&response.content, base_url);
+
+        Ok(FeedUpdate {
+            articles,
+            not_modified: false,
+            etag: response.etag,
+            last_modified: response.last_modified,
+            next_update_time,
+            permanent_redirect: response.permanent_redirect,
+            websub,
+        })
+    }
+
+    /// 回溯RSS源的历史文章
+    ///
+    /// 从源地址开始依次跟随RFC 5005的分页/存档链接，WordPress源没有分页链接时使用?paged=N。
+    /// 达到页数或文章数上限、没有下一页或某一页没有新文章时停止，每获取一页调用一次on_progress。
+    /// 第一页获取失败时返回错误，之后的页面失败时返回已获取的文章
+    pub async fn backfill_feed<F>(
+        &self,
+        feed: &Feed,
+        options: &BackfillOptions,
+        mut on_progress: F,
+    ) -> Result<Vec<Article>, AppError>
+    where
+        F: FnMut(&BackfillProgress),
+    {
+        println!("开始回溯RSS源: {} ({})", feed.name, feed.url);
+
+        let mut articles: Vec<Article> = Vec::new();
+        let mut seen_articles = HashSet::new();
+        let mut visited_pages = HashSet::new();
+        let mut wordpress_page = 1;
+        let mut pages = 0;
+        let mut page_url = Some(feed.url.clone());
+
+        while let Some(url) = page_url.take() {
+            if pages >= options.max_pages
+                || articles.len() >= options.max_articles
+                || !visited_pages.insert(url.clone())
+            {
+                break;
+            }
+            if pages > 0 {
+                // 与更新时相同的同主机请求间隔
+                tokio::time::sleep(self.limits.per_host_delay).await;
+            }
+
+            println!("  正在获取第 {} 页: {}", pages + 1, url);
+            let page = match self.fetch_page(feed, &url).await {
+                Ok(content) => self
+                    .parser
+                    .parse(&content, &url)
+                    .map(|page_articles| (content, page_articles)),
+                Err(e) => Err(e),
+            };
+            let (content, page_articles) = match page {
+                Ok(page) => page,
+                Err(e) if pages == 0 => return Err(e),
+                Err(e) => {
+                    eprintln!("  获取第 {} 页失败，停止回溯: {}", pages + 1, e);
+                    break;
+                }
+            };
+            pages += 1;
+
+            let before = articles.len();
+            for mut article in page_articles {
+                let key = article.guid.clone().unwrap_or_else(|| article.link.clone());
+                if articles.len() < options.max_articles && seen_articles.insert(key) {
+                    article.feed_id = feed.id;
+                    articles.push(article);
+                }
+            }
+            on_progress(&BackfillProgress {
+                feed_id: feed.id,
+                pages,
+                articles: articles.len(),
+                done: false,
+            });
+
+            // WordPress在超出最后一页时可能返回最后一页的内容，没有新文章时停止
+            if articles.len() == before {
+                break;
+            }
+
+            let links = self.parser.paging_links(&content, &url);
+            page_url = links
+                .next
+                .or_else(|| {
+                    if !links.wordpress {
+                        return None;
+                    }
+                    wordpress_page += 1;
+                    wordpress_page_url(&feed.url, wordpress_page)
+                })
+                .filter(|next| {
+                    let allowed = is_allowed_page_url(&feed.url, next);
+                    if !allowed {
+                        eprintln!("  忽略不允许跟随的分页链接: {}", next);
+                    }
+                    allowed
+                });
+        }
+
+        on_progress(&BackfillProgress {
+            feed_id: feed.id,
+            pages,
+            articles: articles.len(),
+            done: true,
+        });
+        println!(
+            "回溯RSS源 {} 完成：获取 {} 页，{} 篇文章",
+            feed.name,
+            pages,
+            articles.len()
+        );
         Ok(articles)
     }
 
-    /// 并发更新多个RSS源
-    pub async fn update_feeds(
+    /// 解析WebSub推送的源内容，推送的内容通常只包含新增或修改的文章
+    pub fn parse_pushed_content(
         &self,
-        feeds: &[Feed],
-    ) -> Vec<Result<(Feed, Vec<Article>), Box<dyn std::error::Error + Send + Sync>>> {
+        feed: &Feed,
+        content: &[u8],
+        content_type: Option<&str>,
+    ) -> Result<FeedUpdate, AppError> {
+        let content = decode_content(content, content_type);
+        let mut articles = self.parser.parse(&content, &feed.url)?;
+        for article in &mut articles {
+            article.feed_id = feed.id;
+        }
+        Ok(FeedUpdate {
+            articles,
+            websub: self.parser.websub_links(&content, &feed.url),
+            ..Default::default()
+        })
+    }
+
+    /// 获取单个页面的内容，使用源的自定义请求设置，不发送条件请求头
+    ///
+    /// 按源地址而不是页面地址选择获取器，网络源的页面不会使用本地文件或本地命令获取器，
+    /// 本地源只能获取用户填写的源地址本身
+    async fn fetch_page(&self, feed: &Feed, url: &str) -> Result<String, AppError> {
+        let response = if let Some(fetcher) = self.local_fetcher(&feed.url) {
+            if url != feed.url {
+                return Err(AppError::Other(format!(
+                    "本地源不支持获取其他地址: {}",
+                    url
+                )));
+            }
+            fetcher.fetch_conditional(url, None, None).await?
+        } else if feed.always_use_browser {
+            self.reqwest_fetcher
+                .fetch_with_browser(url, &feed.request_options)
+                .await?
+        } else {
+            self.reqwest_fetcher
+                .fetch_with_options(url, None, None, &feed.request_options)
+                .await?
+        };
+        Ok(response.content)
+    }
+
+    /// 下载文章网页并提取正文，替换文章内容，源中的原始内容保存到summary
+    ///
+    /// 下载或提取失败的文章保留原始内容
+    pub async fn fetch_full_content(&self, feed: &Feed, articles: Vec<&mut Article>) {
+        /// 同时下载的文章网页数量
+        const FULL_CONTENT_CONCURRENCY: usize = 4;
+
+        futures::stream::iter(articles)
+            .for_each_concurrent(FULL_CONTENT_CONCURRENCY, |article| async move {
+                if article.link.is_empty() {
+                    return;
+                }
+                let page = match self
+                    .reqwest_fetcher
+                    .fetch_with_options(&article.link, None, None, &feed.request_options)
+                    .await
+                {
+                    Ok(response) => response.content,
+                    Err(e) => {
+                        eprintln!("  获取文章全文失败 {}: {}", article.link, e);
+                        return;
+                    }
+                };
+
+                match extract_main_content(&page) {
+                    Some(content) => {
+                        let content =
+                            sanitize_html(&self.parser.fix_content_images(&content, &article.link));
+                        if article.thumbnail.is_none() {
+                            article.thumbnail =
+                                self.parser.extract_first_image(&content, &article.link);
+                        }
+                        article.summary = Some(std::mem::replace(&mut article.content, content));
+                    }
+                    None => println!("  未能从网页中提取正文: {}", article.link),
+                }
+            })
+            .await;
+    }
+
+    /// 获取并解析RSS源用于预览，不写入数据库
+    pub async fn preview_feed(
+        &self,
+        url: &str,
+        options: &FeedRequestOptions,
+    ) -> Result<FeedPreview, AppError> {
+        const PREVIEW_ARTICLE_LIMIT: usize = 5;
+
+        println!("开始预览RSS源: {}", url);
+        let content = match self.local_fetcher(url) {
+            Some(fetcher) => fetcher.fetch(url).await?,
+            None => {
+                self.reqwest_fetcher
+                    .fetch_with_options(url, None, None, options)
+                    .await?
+                    .content
+            }
+        };
+
+        let (format, title) = self
+            .parser
+            .detect_format(&content)
+            .ok_or_else(|| {
+                AppError::Parse(
+                    "Failed to parse feed: not a valid RSS, Atom or JSON Feed format".to_string(),
+                )
+            })?;
+        let mut articles = self.parser.parse(&content, url)?;
+
+        let item_count = articles.len();
+        let newest_date = articles.iter().map(|article| article.pub_date).max();
+
+        // 按发布时间倒序，只保留前几篇文章
+        articles.sort_by_key(|article| std::cmp::Reverse(article.pub_date));
+        articles.truncate(PREVIEW_ARTICLE_LIMIT);
+
+        println!("预览完成: {} 格式，共 {} 篇文章", format, item_count);
+
+        Ok(FeedPreview {
+            url: url.to_string(),
+            format: format.to_string(),
+            title,
+            item_count,
+            newest_date,
+            articles,
+        })
+    }
+
+    /// 获取用于限流的主机名，无法解析时使用完整URL
+    fn host_key(url: &str) -> String {
+        url::Url::parse(url)
+            .ok()
+            .and_then(|parsed| parsed.host_str().map(|host| host.to_ascii_lowercase()))
+            .unwrap_or_else(|| url.to_string())
+    }
+
+    /// 并发更新多个RSS源
+    pub async fn update_feeds(&self, feeds: &[Feed]) -> Vec<Result<(Feed, FeedUpdate), AppError>> {
         let total_feeds = feeds.len();
-        println!("开始并发更新 {} 个RSS源", total_feeds);
+        println!(
+            "开始并发更新 {} 个RSS源 (并发上限: {}, 每主机上限: {}, 每主机间隔: {}ms)",
+            total_feeds,
+            self.limits.max_concurrency,
+            self.limits.per_host_concurrency,
+            self.limits.per_host_delay.as_millis()
+        );
 
         let mut tasks = Vec::with_capacity(total_feeds);
 
+        // 全局并发限制和按主机划分的请求限制
+        let global_permits = Arc::new(Semaphore::new(self.limits.max_concurrency.max(1)));
+        let mut host_limiters: HashMap<String, Arc<HostLimiter>> = HashMap::new();
+
         for feed in feeds {
             let feed_clone = feed.clone();
             let updater_clone = self.clone();
+            let global_permits = global_permits.clone();
+            let host_limiter = host_limiters
+                .entry(Self::host_key(&feed.url))
+                .or_insert_with(|| {
+                    Arc::new(HostLimiter {
+                        permits: Semaphore::new(self.limits.per_host_concurrency.max(1)),
+                        last_request: Mutex::new(None),
+                    })
+                })
+                .clone();
+            let per_host_delay = self.limits.per_host_delay;
+
             tasks.push(tokio::spawn(async move {
+                // 先获取主机许可，避免占用全局许可等待同一主机
+                let _host_permit = host_limiter.permits.acquire().await;
+                let _global_permit = global_permits.acquire_owned().await;
+
+                // 保证同一主机两次请求之间的最小间隔，在取得全局许可后、即将发送请求时记录时间，
+                // 避免等待全局许可的同一主机请求在许可释放后连续发出
+                {
+                    let mut last_request = host_limiter.last_request.lock().await;
+                    if let Some(last_time) = *last_request {
+                        tokio::time::sleep_until(last_time + per_host_delay).await;
+                    }
+                    *last_request = Some(Instant::now());
+                }
+
                 let result = updater_clone.update_feed(&feed_clone).await;
                 (feed_clone, result)
             }));
@@ -625,9 +2307,9 @@ impl RssUpdater {
 
         for task in tasks {
             match task.await {
-                Ok((feed, Ok(articles))) => {
+                Ok((feed, Ok(update))) => {
                     success_count += 1;
-                    results.push(Ok((feed, articles)));
+                    results.push(Ok((feed, update)));
                 }
                 Ok((_feed, Err(e))) => {
                     failure_count += 1;
@@ -635,7 +2317,7 @@ impl RssUpdater {
                 }
                 Err(e) => {
                     failure_count += 1;
-                    results.push(Err(Box::new(e) as Box<dyn std::error::Error + Send + Sync>));
+                    results.push(Err(AppError::Other(format!("更新任务执行失败: {}", e))));
                 }
             }
         }
@@ -651,9 +2333,519 @@ impl RssUpdater {
 
 impl Clone for RssUpdater {
     fn clone(&self) -> Self {
+        Self {
+            reqwest_fetcher: self.reqwest_fetcher.clone(),
+            file_fetcher: self.file_fetcher.clone(),
+            exec_fetcher: self.exec_fetcher.clone(),
+            parser: self.parser.clone(),
+            limits: self.limits.clone(),
+        }
+    }
+}
+
+/// 常见的RSS源路径，用于网页未声明<link rel="alternate">时探测
+const COMMON_FEED_PATHS: &[&str] = &[
+    "/feed",
+    "/rss",
+    "/feed.xml",
+    "/rss.xml",
+    "/atom.xml",
+    "/index.xml",
+    "/feed.json",
+];
+
+/// RSS源自动发现器
+pub struct FeedDiscoverer {
+    reqwest_fetcher: ReqwestFetcher,
+    parser: RssParser,
+}
+
+impl FeedDiscoverer {
+    pub fn new() -> Self {
         Self {
             reqwest_fetcher: ReqwestFetcher::new(),
             parser: RssParser::new(),
         }
     }
+
+    /// 设置代理和CA证书
+    pub fn with_network(mut self, network: NetworkOptions) -> Self {
+        self.reqwest_fetcher = self.reqwest_fetcher.with_network(network);
+        self
+    }
+
+    /// 根据网站或RSS源地址发现可订阅的RSS源
+    pub async fn discover(&self, url: &str) -> Result<Vec<FeedCandidate>, AppError> {
+        // 用户未输入协议时默认使用https
+        let url = if url.contains("://") {
+            url.trim().to_string()
+        } else {
+            format!("https://{}", url.trim())
+        };
+        let page_url = url::Url::parse(&url)?;
+
+        println!("开始发现RSS源: {}", url);
+        // 发现过程不回退到浏览器，只使用普通请求
+        let content = self.reqwest_fetcher.fetch_plain(&url).await?;
+
+        // 地址本身就是RSS源
+        if let Some((format, title)) = self.parser.detect_format(&content) {
+            println!("地址本身是 {} 格式的RSS源", format);
+            return Ok(vec![FeedCandidate {
+                url,
+                title,
+                format: format.to_string(),
+            }]);
+        }
+
+        // 从HTML页面的<link rel="alternate">标签中查找RSS源
+        let mut candidates = Self::extract_link_candidates(&content, &page_url);
+        println!("从页面<link>标签中发现 {} 个RSS源", candidates.len());
+        if !candidates.is_empty() {
+            return Ok(candidates);
+        }
+
+        // 页面未声明RSS源时，探测常见的RSS源路径
+        let probe_urls: Vec<String> = COMMON_FEED_PATHS
+            .iter()
+            .filter_map(|path| page_url.join(path).ok())
+            .map(|probe_url| probe_url.to_string())
+            .collect();
+
+        let probes = probe_urls.iter().map(|probe_url| async move {
+            let content = self.reqwest_fetcher.fetch_plain(probe_url).await.ok()?;
+            let (format, title) = self.parser.detect_format(&content)?;
+            Some(FeedCandidate {
+                url: probe_url.clone(),
+                title,
+                format: format.to_string(),
+            })
+        });
+
+        for candidate in futures::future::join_all(probes)
+            .await
+            .into_iter()
+            .flatten()
+        {
+            println!("通过常见路径发现RSS源: {}", candidate.url);
+            candidates.push(candidate);
+        }
+
+        println!("共发现 {} 个RSS源", candidates.len());
+        Ok(candidates)
+    }
+
+    /// 从HTML页面中提取<link rel="alternate">声明的RSS源
+    fn extract_link_candidates(content: &str, page_url: &url::Url) -> Vec<FeedCandidate> {
+        let document = scraper::Html::parse_document(content);
+        let link_selector = scraper::Selector::parse("link[rel][href]").unwrap();
+        let title_selector = scraper::Selector::parse("title").unwrap();
+
+        // 页面标题，作为<link>未提供title时的备用名称
+        let page_title = document
+            .select(&title_selector)
+            .next()
+            .map(|title| title.text().collect::<String>().trim().to_string())
+            .filter(|title| !title.is_empty());
+
+        let mut candidates: Vec<FeedCandidate> = Vec::new();
+        for link in document.select(&link_selector) {
+            let element = link.value();
+            let is_alternate = element
+                .attr("rel")
+                .map(|rel| {
+                    rel.split_whitespace()
+                        .any(|r| r.eq_ignore_ascii_case("alternate"))
+                })
+                .unwrap_or(false);
+            if !is_alternate {
+                continue;
+            }
+
+            let format = match element.attr("type").map(|t| t.trim().to_ascii_lowercase()) {
+                Some(t) if t == "application/rss+xml" => "rss",
+                Some(t) if t == "application/atom+xml" => "atom",
+                Some(t) if t == "application/feed+json" || t == "application/json" => "json",
+                _ => continue,
+            };
+
+            let Some(href) = element.attr("href") else {
+                continue;
+            };
+            let Ok(feed_url) = page_url.join(href.trim()) else {
+                continue;
+            };
+            // 页面只能声明网络源，不能让用户添加file:或exec:地址
+            if !matches!(feed_url.scheme(), "http" | "https") {
+                continue;
+            }
+            let feed_url = feed_url.to_string();
+
+            if candidates.iter().any(|c| c.url == feed_url) {
+                continue;
+            }
+
+            let title = element
+                .attr("title")
+                .map(|title| title.trim().to_string())
+                .filter(|title| !title.is_empty())
+                .or_else(|| page_title.clone());
+
+            candidates.push(FeedCandidate {
+                url: feed_url,
+                title,
+                format: format.to_string(),
+            });
+        }
+
+        candidates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn schedules_without_hints_immediately() {
+        let now = at("2024-05-01T10:30:00Z");
+        assert_eq!(ScheduleHints::default().next_update_time(now), None);
+    }
+
+    #[test]
+    fn schedules_after_min_interval() {
+        let now = at("2024-05-01T10:30:00Z");
+        let hints = ScheduleHints {
+            min_interval: Some(chrono::Duration::minutes(90)),
+            ..Default::default()
+        };
+        assert_eq!(
+            hints.next_update_time(now),
+            Some(at("2024-05-01T12:00:00Z"))
+        );
+
+        // 最小间隔不超过24小时
+        let hints = ScheduleHints {
+            min_interval: Some(chrono::Duration::weeks(1)),
+            ..Default::default()
+        };
+        assert_eq!(
+            hints.next_update_time(now),
+            Some(at("2024-05-02T10:30:00Z"))
+        );
+    }
+
+    #[test]
+    fn skips_hours_and_days() {
+        let now = at("2024-05-01T10:30:00Z");
+        let hints = ScheduleHints {
+            skip_hours: vec![10, 11, 12],
+            ..Default::default()
+        };
+        assert_eq!(
+            hints.next_update_time(now),
+            Some(at("2024-05-01T13:00:00Z"))
+        );
+
+        // 2024-05-01为星期三
+        let hints = ScheduleHints {
+            skip_days: vec![Weekday::Wed, Weekday::Thu],
+            ..Default::default()
+        };
+        assert_eq!(
+            hints.next_update_time(now),
+            Some(at("2024-05-03T00:00:00Z"))
+        );
+
+        let hints = ScheduleHints {
+            min_interval: Some(chrono::Duration::hours(14)),
+            skip_hours: vec![0, 1],
+            skip_days: vec![Weekday::Wed],
+        };
+        assert_eq!(
+            hints.next_update_time(now),
+            Some(at("2024-05-02T02:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn gives_up_when_every_hour_is_skipped() {
+        let now = at("2024-05-01T10:30:00Z");
+        let hints = ScheduleHints {
+            skip_hours: (0..24).collect(),
+            ..Default::default()
+        };
+        // 最多推进一周
+        assert_eq!(
+            hints.next_update_time(now),
+            Some(at("2024-05-08T10:00:00Z"))
+        );
+    }
+
+    #[test]
+    fn reads_schedule_hints_from_channel() {
+        let content = r#"<rss version="2.0" xmlns:sy="http://purl.org/rss/1.0/modules/syndication/">
+            <channel><title>t</title><link>https://example.com</link><description>d</description>
+            <ttl>30</ttl><sy:updatePeriod>hourly</sy:updatePeriod><sy:updateFrequency>1</sy:updateFrequency>
+            <skipHours><hour>1</hour><hour>24</hour><hour>x</hour></skipHours>
+            <skipDays><day>Sunday</day></skipDays></channel></rss>"#;
+        let hints = RssParser::new().schedule_hints(content);
+        assert_eq!(hints.min_interval, Some(chrono::Duration::hours(1)));
+        assert_eq!(hints.skip_hours, vec![1]);
+        assert_eq!(hints.skip_days, vec![Weekday::Sun]);
+
+        let hints = RssParser::new().schedule_hints("not a feed");
+        assert_eq!(hints.min_interval, None);
+        assert!(hints.skip_hours.is_empty());
+    }
+
+    fn gbk(text: &str) -> Vec<u8> {
+        encoding_rs::GBK.encode(text).0.into_owned()
+    }
+
+    #[test]
+    fn allows_only_http_page_urls() {
+        let feed = "https://example.com/feed";
+        assert!(is_allowed_page_url(feed, "https://example.com/feed?page=2"));
+        assert!(is_allowed_page_url(feed, "http://archive.example.com/2020"));
+        assert!(!is_allowed_page_url(feed, "file:///etc/passwd"));
+        assert!(!is_allowed_page_url(feed, "exec:backup"));
+        assert!(!is_allowed_page_url(feed, "ftp://example.com/feed"));
+        assert!(!is_allowed_page_url(feed, "not a url"));
+
+        // 本地源不跟随分页链接
+        let feed = "file:///home/user/feed.xml";
+        assert!(!is_allowed_page_url(feed, "file:///home/user/feed-2.xml"));
+        assert!(!is_allowed_page_url(feed, "https://example.com/feed"));
+        assert!(!is_allowed_page_url("exec:news", "exec:backup"));
+    }
+
+    fn feed(url: &str) -> Feed {
+        Feed {
+            id: 1,
+            name: "test".to_string(),
+            url: url.to_string(),
+            group_id: None,
+            last_updated: None,
+            translate_enabled: false,
+            notification_enabled: false,
+            last_update_status: None,
+            update_attempts: 0,
+            next_retry_time: None,
+            etag: None,
+            last_modified: None,
+            update_interval: None,
+            last_error_kind: None,
+            last_http_status: None,
+            always_use_browser: false,
+            request_options: Default::default(),
+            fetch_full_content: false,
+        }
+    }
+
+    /// 在本地端口上提供一个RSS源，返回源地址
+    async fn serve_feed(body: String) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/rss+xml\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        format!("http://{}/feed", addr)
+    }
+
+    fn rss_with_next(title: &str, next: &str) -> String {
+        format!(
+            r#"<?xml version="1.0"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom"><channel>
+<title>{title}</title><link>https://example.com/</link>
+<atom:link rel="next" href="{next}"/>
+<item><title>{title}</title><link>https://example.com/{title}</link><guid>{title}</guid></item>
+</channel></rss>"#
+        )
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn backfill_does_not_follow_remote_links_to_local_sources() {
+        use crate::local::ExecCommand;
+
+        let dir = std::env::temp_dir().join(format!("rss-backfill-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let leaked = dir.join("leaked.xml");
+        std::fs::write(&leaked, rss_with_next("leaked-file", "")).unwrap();
+        let file_url = url::Url::from_file_path(&leaked).unwrap().to_string();
+
+        let mut commands = HashMap::new();
+        commands.insert(
+            "leak".to_string(),
+            ExecCommand {
+                program: "cat".to_string(),
+                args: vec![leaked.to_string_lossy().to_string()],
+                dir: None,
+            },
+        );
+        let updater =
+            RssUpdater::new().with_exec(ExecFetcher::new(commands, ExecFetcher::DEFAULT_TIMEOUT));
+
+        for next in [file_url.as_str(), "exec:leak"] {
+            let url = serve_feed(rss_with_next("remote", next)).await;
+            let articles = updater
+                .backfill_feed(&feed(&url), &BackfillOptions::default(), |_| {})
+                .await
+                .unwrap();
+            let titles: Vec<&str> = articles.iter().map(|a| a.title.as_str()).collect();
+            assert_eq!(titles, ["remote"], "next: {}", next);
+
+            // 即使分页链接通过了过滤，网络源的页面也不会使用本地获取器
+            assert!(updater.fetch_page(&feed(&url), next).await.is_err());
+        }
+
+        // 本地源只能获取源地址本身
+        let local = feed(&file_url);
+        assert!(updater.fetch_page(&local, &file_url).await.is_ok());
+        assert!(updater.fetch_page(&local, "exec:leak").await.is_err());
+        let local = feed("exec:other");
+        assert!(updater.fetch_page(&local, &file_url).await.is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn drops_enclosures_without_http_urls() {
+        let enclosure = |url: &str| Enclosure {
+            url: url.to_string(),
+            mime_type: Some("audio/mpeg".to_string()),
+            ..Default::default()
+        };
+        let enclosures = RssParser::new().normalize_enclosures(
+            vec![
+                enclosure("javascript:alert(1)"),
+                enclosure("file:///etc/passwd"),
+                enclosure("data:audio/mpeg;base64,AAAA"),
+                enclosure("JavaScript:alert(1)"),
+                enclosure("/episodes/1.mp3"),
+                enclosure("//cdn.example.com/2.mp3"),
+            ],
+            "https://example.com/feed",
+        );
+        let urls: Vec<&str> = enclosures.iter().map(|e| e.url.as_str()).collect();
+        assert_eq!(
+            urls,
+            [
+                "https://example.com/episodes/1.mp3",
+                "https://cdn.example.com/2.mp3"
+            ]
+        );
+    }
+
+    #[test]
+    fn link_candidates_skip_local_urls() {
+        let page = url::Url::parse("https://example.com/blog/").unwrap();
+        let html = r#"<html><head>
+<link rel="alternate" type="application/rss+xml" href="file:///etc/passwd">
+<link rel="alternate" type="application/rss+xml" href="exec:leak">
+<link rel="alternate" type="application/atom+xml" href="atom.xml">
+</head></html>"#;
+        let candidates = FeedDiscoverer::extract_link_candidates(html, &page);
+        let urls: Vec<&str> = candidates.iter().map(|c| c.url.as_str()).collect();
+        assert_eq!(urls, ["https://example.com/blog/atom.xml"]);
+    }
+
+    #[test]
+    fn decodes_utf8_by_default() {
+        assert_eq!(
+            decode_content("<rss>中文</rss>".as_bytes(), None),
+            "<rss>中文</rss>"
+        );
+        assert_eq!(
+            decode_content("<rss>中文</rss>".as_bytes(), Some("application/xml")),
+            "<rss>中文</rss>"
+        );
+    }
+
+    #[test]
+    fn decodes_bom() {
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(
+            "<?xml version=\"1.0\" encoding=\"UTF-16\"?><rss>中文</rss>"
+                .encode_utf16()
+                .flat_map(u16::to_le_bytes),
+        );
+        assert_eq!(
+            decode_content(&bytes, Some("text/xml; charset=gbk")),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><rss>中文</rss>"
+        );
+
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice("<rss>中文</rss>".as_bytes());
+        assert_eq!(decode_content(&bytes, None), "<rss>中文</rss>");
+    }
+
+    #[test]
+    fn decodes_content_type_charset() {
+        assert_eq!(
+            decode_content(&gbk("<rss>中文</rss>"), Some("text/xml; charset=\"GBK\"")),
+            "<rss>中文</rss>"
+        );
+    }
+
+    #[test]
+    fn decodes_xml_declaration_encoding() {
+        let bytes = gbk("<?xml version=\"1.0\" encoding='gb2312'?>\n<rss>中文</rss>");
+        assert_eq!(
+            decode_content(&bytes, None),
+            "<?xml version=\"1.0\" encoding='UTF-8'?>\n<rss>中文</rss>"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_declared_encoding_when_header_is_wrong() {
+        let bytes = gbk("<?xml version=\"1.0\" encoding=\"GBK\"?><rss>中文</rss>");
+        assert_eq!(
+            decode_content(&bytes, Some("text/xml; charset=utf-8")),
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?><rss>中文</rss>"
+        );
+    }
+
+    #[test]
+    fn normalizes_xml_declaration() {
+        assert_eq!(
+            normalize_xml_declaration(
+                "  <?xml version=\"1.0\" encoding = \"ISO-8859-1\" ?><rss/>".to_string()
+            ),
+            "  <?xml version=\"1.0\" encoding = \"UTF-8\" ?><rss/>"
+        );
+        assert_eq!(
+            normalize_xml_declaration(
+                "<?xml version=\"1.0\" encoding=\"utf-8\"?><rss/>".to_string()
+            ),
+            "<?xml version=\"1.0\" encoding=\"utf-8\"?><rss/>"
+        );
+        // 没有声明或声明中没有encoding时保持不变
+        assert_eq!(
+            normalize_xml_declaration("<?xml version=\"1.0\"?><rss/>".to_string()),
+            "<?xml version=\"1.0\"?><rss/>"
+        );
+        assert_eq!(
+            normalize_xml_declaration("<rss><title>encoding=\"gbk\"</title></rss>".to_string()),
+            "<rss><title>encoding=\"gbk\"</title></rss>"
+        );
+    }
 }
@@ -0,0 +1,447 @@
+use html_escape::{encode_double_quoted_attribute, encode_text};
+use scraper::{ElementRef, Html, Node};
+
+/// 允许保留的标签，其他标签只保留其中的内容
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "article",
+    "aside",
+    "audio",
+    "b",
+    "bdi",
+    "bdo",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "col",
+    "colgroup",
+    "dd",
+    "del",
+    "details",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "footer",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "header",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "mark",
+    "ol",
+    "p",
+    "picture",
+    "pre",
+    "q",
+    "rp",
+    "rt",
+    "ruby",
+    "s",
+    "samp",
+    "section",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "sub",
+    "summary",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "track",
+    "u",
+    "ul",
+    "var",
+    "video",
+    "wbr",
+];
+
+/// 连同内容一起丢弃的标签
+const DROPPED_TAGS: &[&str] = &[
+    "script", "style", "iframe", "frame", "frameset", "object", "embed", "applet", "noscript",
+    "template", "svg", "math", "form", "input", "button", "select", "textarea", "link", "meta",
+    "base", "head", "title",
+];
+
+/// 没有结束标签的元素
+const VOID_TAGS: &[&str] = &["br", "col", "hr", "img", "source", "track", "wbr"];
+
+/// 所有标签都允许的属性
+const GLOBAL_ATTRIBUTES: &[&str] = &["title", "lang", "dir"];
+
+/// 各标签额外允许的属性
+const TAG_ATTRIBUTES: &[(&str, &[&str])] = &[
+    ("a", &["href"]),
+    ("img", &["src", "srcset", "sizes", "alt", "width", "height"]),
+    ("video", &["src", "poster", "controls", "width", "height"]),
+    ("audio", &["src", "controls"]),
+    ("source", &["src", "srcset", "sizes", "media", "type"]),
+    ("track", &["src", "kind", "label", "srclang"]),
+    ("td", &["colspan", "rowspan"]),
+    ("th", &["colspan", "rowspan", "scope"]),
+    ("ol", &["start", "reversed"]),
+    ("time", &["datetime"]),
+    ("blockquote", &["cite"]),
+    ("q", &["cite"]),
+    // 代码块的语言标记，用于语法高亮
+    ("code", &["class"]),
+];
+
+/// 值为URL的属性，需要检查协议
+const URL_ATTRIBUTES: &[&str] = &["href", "src", "poster", "cite"];
+
+/// 常见的统计像素和广告跟踪地址
+const TRACKER_PATTERNS: &[&str] = &[
+    "feeds.feedburner.com/~r/",
+    "feedsportal.com",
+    "pixel.wp.com",
+    "stats.wordpress.com",
+    "pixel.quantserve.com",
+    "doubleclick.net",
+    "google-analytics.com",
+    "/tracking/pixel",
+    "/open.gif",
+    "/beacon.gif",
+];
+
+/// 清理文章HTML，只保留白名单中的标签和属性
+///
+/// 去掉脚本、样式、iframe、事件处理属性、javascript:等危险URL以及统计像素，
+/// 用于源中的原始内容和AI翻译后的内容
+pub fn sanitize_html(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let mut out = String::with_capacity(html.len());
+    serialize_children(fragment.root_element(), &mut out);
+    out
+}
+
+/// 判断图片是否为统计像素（1x1图片或已知的跟踪地址）
+pub fn is_tracking_pixel(element: ElementRef) -> bool {
+    let is_tiny = |attr: &str| {
+        element
+            .value()
+            .attr(attr)
+            .and_then(|value| value.trim().trim_end_matches("px").parse::<u32>().ok())
+            .is_some_and(|size| size <= 1)
+    };
+    if is_tiny("width") && is_tiny("height") {
+        return true;
+    }
+
+    element.value().attr("src").is_some_and(is_tracker_url)
+}
+
+/// 判断地址是否为常见的统计像素或广告跟踪地址
+pub fn is_tracker_url(url: &str) -> bool {
+    TRACKER_PATTERNS.iter().any(|pattern| url.contains(pattern))
+}
+
+/// 检查URL协议，只允许http(s)、mailto、相对地址和图片data URL
+fn is_safe_url(attr: &str, value: &str) -> bool {
+    // 去掉空白和控制字符，防止"java\tscript:"之类的绕过
+    let normalized: String = value
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect::<String>()
+        .to_lowercase();
+
+    let Some(colon) = normalized.find(':') else {
+        return true;
+    };
+    // 冒号出现在路径、查询或片段中时为相对地址
+    if normalized[..colon].contains(['/', '?', '#']) {
+        return true;
+    }
+
+    let scheme = &normalized[..colon];
+    match scheme {
+        "http" | "https" => true,
+        "mailto" => attr == "href",
+        "data" => {
+            attr == "src"
+                && [
+                    "data:image/png",
+                    "data:image/jpeg",
+                    "data:image/gif",
+                    "data:image/webp",
+                ]
+                .iter()
+                .any(|prefix| normalized.starts_with(prefix))
+        }
+        _ => false,
+    }
+}
+
+/// 清理srcset属性，逐个检查候选图片的URL，去掉不安全和跟踪地址的候选
+///
+/// 每个候选为"URL [描述符]"，以逗号分隔；URL中可以包含逗号（如data URL），
+/// 因此先读取到空白为止的URL，再读取到逗号为止的描述符。没有剩余候选时返回None
+fn sanitize_srcset(value: &str) -> Option<String> {
+    let mut candidates = Vec::new();
+    let mut rest = value;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() {
+            break;
+        }
+
+        let url_end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let (mut url, after) = rest.split_at(url_end);
+        let descriptor = if url.ends_with(',') {
+            url = url.trim_end_matches(',');
+            rest = after;
+            ""
+        } else {
+            let descriptor_end = after.find(',').unwrap_or(after.len());
+            rest = &after[descriptor_end..];
+            after[..descriptor_end].trim()
+        };
+
+        if !is_safe_url("src", url) || is_tracker_url(url) {
+            continue;
+        }
+        if descriptor.is_empty() {
+            candidates.push(url.to_string());
+        } else {
+            candidates.push(format!("{} {}", url, descriptor));
+        }
+    }
+
+    (!candidates.is_empty()).then(|| candidates.join(", "))
+}
+
+/// 是否允许该标签上的属性
+fn is_allowed_attribute(tag: &str, attr: &str) -> bool {
+    GLOBAL_ATTRIBUTES.contains(&attr)
+        || TAG_ATTRIBUTES
+            .iter()
+            .any(|(name, attrs)| *name == tag && attrs.contains(&attr))
+}
+
+/// 递归输出清理后的子节点
+fn serialize_children(element: ElementRef, out: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(&encode_text(&text.text)),
+            Node::Element(_) => {
+                let Some(child) = ElementRef::wrap(child) else {
+                    continue;
+                };
+                let name = child.value().name();
+                if DROPPED_TAGS.contains(&name) {
+                    continue;
+                }
+                if !ALLOWED_TAGS.contains(&name) {
+                    // 未知标签去掉标签本身，保留内容
+                    serialize_children(child, out);
+                    continue;
+                }
+                if name == "img" && is_tracking_pixel(child) {
+                    continue;
+                }
+
+                out.push('<');
+                out.push_str(name);
+                for (attr, value) in child.value().attrs() {
+                    if !is_allowed_attribute(name, attr) {
+                        continue;
+                    }
+                    if URL_ATTRIBUTES.contains(&attr) && !is_safe_url(attr, value) {
+                        continue;
+                    }
+                    let value = if attr == "srcset" {
+                        let Some(srcset) = sanitize_srcset(value) else {
+                            continue;
+                        };
+                        srcset
+                    } else {
+                        value.to_string()
+                    };
+                    out.push_str(&format!(
+                        " {}=\"{}\"",
+                        attr,
+                        encode_double_quoted_attribute(&value)
+                    ));
+                }
+                if name == "a" {
+                    // 外部链接不向目标页面暴露来源
+                    out.push_str(" rel=\"noopener noreferrer\"");
+                }
+                out.push('>');
+                if VOID_TAGS.contains(&name) {
+                    continue;
+                }
+                serialize_children(child, out);
+                out.push_str(&format!("</{}>", name));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_allowed_tags_and_attributes() {
+        assert_eq!(
+            sanitize_html(r#"<p class="x" title="t">文字<b>加粗</b></p>"#),
+            r#"<p title="t">文字<b>加粗</b></p>"#
+        );
+        assert_eq!(
+            sanitize_html(r#"<a href="https://example.com/a?b=1&c=2" target="_blank">链接</a>"#),
+            r#"<a href="https://example.com/a?b=1&amp;c=2" rel="noopener noreferrer">链接</a>"#
+        );
+        assert_eq!(
+            sanitize_html(r#"<pre><code class="language-rust">fn main() {}</code></pre>"#),
+            r#"<pre><code class="language-rust">fn main() {}</code></pre>"#
+        );
+    }
+
+    #[test]
+    fn drops_dangerous_urls() {
+        assert_eq!(
+            sanitize_html(r#"<a href="javascript:alert(1)">链接</a>"#),
+            r#"<a rel="noopener noreferrer">链接</a>"#
+        );
+        assert_eq!(
+            sanitize_html("<a href=\"java\tscript:alert(1)\">链接</a>"),
+            r#"<a rel="noopener noreferrer">链接</a>"#
+        );
+        assert_eq!(
+            sanitize_html(r#"<a href=" JAVASCRIPT:alert(1)">链接</a>"#),
+            r#"<a rel="noopener noreferrer">链接</a>"#
+        );
+        assert_eq!(
+            sanitize_html(r#"<img src="data:text/html;base64,PHNjcmlwdD4=">"#),
+            "<img>"
+        );
+        assert_eq!(
+            sanitize_html(r#"<video poster="vbscript:msgbox(1)"></video>"#),
+            "<video></video>"
+        );
+    }
+
+    #[test]
+    fn keeps_safe_urls() {
+        assert!(is_safe_url("href", "/relative/path"));
+        assert!(is_safe_url("href", "page.html?time=10:00"));
+        assert!(is_safe_url("href", "mailto:someone@example.com"));
+        assert!(!is_safe_url("src", "mailto:someone@example.com"));
+        assert!(is_safe_url("src", "data:image/png;base64,iVBORw0KGgo="));
+        assert!(!is_safe_url("href", "data:image/png;base64,iVBORw0KGgo="));
+        assert!(!is_safe_url("src", "file:///etc/passwd"));
+    }
+
+    #[test]
+    fn drops_event_handlers() {
+        assert_eq!(
+            sanitize_html(r#"<img src="a.png" onerror="alert(1)" onload="alert(2)">"#),
+            r#"<img src="a.png">"#
+        );
+        assert_eq!(
+            sanitize_html(r#"<div onclick="alert(1)" style="color:red">内容</div>"#),
+            "<div>内容</div>"
+        );
+    }
+
+    #[test]
+    fn drops_tracking_pixels() {
+        assert_eq!(
+            sanitize_html(
+                r#"<p>正文<img src="https://example.com/p.gif" width="1" height="1"></p>"#
+            ),
+            "<p>正文</p>"
+        );
+        assert_eq!(
+            sanitize_html(r#"<img src="https://pixel.wp.com/g.gif?blog=1">"#),
+            ""
+        );
+        assert_eq!(
+            sanitize_html(r#"<img src="https://example.com/a.png" width="1" height="300">"#),
+            r#"<img height="300" src="https://example.com/a.png" width="1">"#
+        );
+    }
+
+    #[test]
+    fn drops_dangerous_tags_with_content() {
+        assert_eq!(
+            sanitize_html("<p>前<script>alert(1)</script>后</p><style>p{}</style>"),
+            "<p>前后</p>"
+        );
+        // 丢弃标签中嵌套的内容也一起丢弃
+        assert_eq!(
+            sanitize_html(
+                r#"<div><iframe src="https://example.com"><p>内容</p></iframe>正文</div>"#
+            ),
+            "<div>正文</div>"
+        );
+        assert_eq!(
+            sanitize_html(r#"<noscript><img src="a.png"><b>提示</b></noscript>"#),
+            ""
+        );
+        // 未知标签只去掉标签本身
+        assert_eq!(
+            sanitize_html("<custom><b>加粗<script>x</script></b></custom>"),
+            "<b>加粗</b>"
+        );
+    }
+
+    #[test]
+    fn sanitizes_srcset() {
+        assert_eq!(
+            sanitize_html(
+                r#"<img src="a.png" srcset="a-1x.png 1x, javascript:alert(1) 2x, https://example.com/a-3x.png 3x">"#
+            ),
+            r#"<img src="a.png" srcset="a-1x.png 1x, https://example.com/a-3x.png 3x">"#
+        );
+        assert_eq!(
+            sanitize_html(
+                r#"<picture><source srcset="a.webp 480w,b.webp 800w" sizes="50vw" type="image/webp"><img src="a.png"></picture>"#
+            ),
+            r#"<picture><source sizes="50vw" srcset="a.webp 480w, b.webp 800w" type="image/webp"><img src="a.png"></picture>"#
+        );
+        // 没有安全的候选时去掉整个属性
+        assert_eq!(
+            sanitize_html(
+                r#"<img src="a.png" srcset="javascript:alert(1), https://pixel.wp.com/g.gif 2x">"#
+            ),
+            r#"<img src="a.png">"#
+        );
+    }
+
+    #[test]
+    fn parses_srcset_candidates() {
+        assert_eq!(
+            sanitize_srcset("data:image/png;base64,iVBORw0KGgo= 1x, b.png 2x").as_deref(),
+            Some("data:image/png;base64,iVBORw0KGgo= 1x, b.png 2x")
+        );
+        assert_eq!(
+            sanitize_srcset("a.png, b.png 2x,").as_deref(),
+            Some("a.png, b.png 2x")
+        );
+        assert_eq!(sanitize_srcset("  ,  "), None);
+    }
+}
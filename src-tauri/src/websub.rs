@@ -0,0 +1,616 @@
+use crate::error::AppError;
+use async_trait::async_trait;
+use hmac::digest::KeyInit;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use sha1::Sha1;
+use sha2::{Sha256, Sha384, Sha512};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::time::{Duration, timeout};
+
+/// 回调地址的路径前缀，完整路径为/websub/{token}
+pub const CALLBACK_PATH: &str = "/websub/";
+
+/// 请求头的大小上限
+const MAX_HEADER_SIZE: usize = 16 * 1024;
+/// 推送内容的大小上限
+const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
+/// 单个连接的处理超时
+const CONNECTION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 生成订阅的回调地址
+pub fn callback_url(base_url: &str, token: &str) -> String {
+    format!(
+        "{}{}{}",
+        base_url.trim_end_matches('/'),
+        CALLBACK_PATH,
+        token
+    )
+}
+
+/// 向推送中心发送订阅请求
+///
+/// 推送中心接受请求后会异步访问回调地址确认订阅意图，确认后订阅才生效。
+/// secret用于推送中心对推送内容签名
+pub async fn subscribe(
+    client: &Client,
+    hub: &str,
+    topic: &str,
+    callback: &str,
+    secret: &str,
+    lease_seconds: Option<u64>,
+) -> Result<(), AppError> {
+    let lease_seconds = lease_seconds.map(|seconds| seconds.to_string());
+    let mut form = vec![
+        ("hub.mode", "subscribe"),
+        ("hub.topic", topic),
+        ("hub.callback", callback),
+        ("hub.secret", secret),
+    ];
+    if let Some(lease_seconds) = &lease_seconds {
+        form.push(("hub.lease_seconds", lease_seconds));
+    }
+
+    let response = client.post(hub).form(&form).send().await?;
+    if !response.status().is_success() {
+        return Err(AppError::Http {
+            status: response.status().as_u16(),
+            url: hub.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// 校验推送内容的X-Hub-Signature签名
+///
+/// 签名格式为"method=hex"，method支持sha1、sha256、sha384和sha512
+pub fn verify_signature(secret: &str, signature: &str, content: &[u8]) -> bool {
+    let Some((method, hex)) = signature.trim().split_once('=') else {
+        return false;
+    };
+    let Some(expected) = decode_hex(hex) else {
+        return false;
+    };
+    match method.to_ascii_lowercase().as_str() {
+        "sha1" => verify_hmac::<Hmac<Sha1>>(secret, content, &expected),
+        "sha256" => verify_hmac::<Hmac<Sha256>>(secret, content, &expected),
+        "sha384" => verify_hmac::<Hmac<Sha384>>(secret, content, &expected),
+        "sha512" => verify_hmac::<Hmac<Sha512>>(secret, content, &expected),
+        _ => false,
+    }
+}
+
+/// 计算HMAC并以固定时间比较
+fn verify_hmac<M: Mac + KeyInit>(secret: &str, content: &[u8], expected: &[u8]) -> bool {
+    let Ok(mut mac) = <M as KeyInit>::new_from_slice(secret.as_bytes()) else {
+        return false;
+    };
+    mac.update(content);
+    mac.verify_slice(expected).is_ok()
+}
+
+/// 解析十六进制字符串
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    // from_str_radix接受"+"号，需要先检查字符
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// 推送中心确认订阅意图时的参数
+#[derive(Debug, Clone)]
+pub struct IntentVerification {
+    /// subscribe、unsubscribe，或推送中心拒绝订阅时的denied
+    pub mode: String,
+    pub topic: String,
+    /// 需要原样返回的随机字符串（denied时没有）
+    pub challenge: Option<String>,
+    /// 推送中心给出的订阅有效期（秒）
+    pub lease_seconds: Option<i64>,
+    /// 拒绝订阅的原因
+    pub reason: Option<String>,
+}
+
+/// 回调服务器的请求处理
+#[async_trait]
+pub trait CallbackHandler: Send + Sync + 'static {
+    /// 确认订阅意图，返回true时回应challenge，返回false时回应404
+    async fn verify_intent(&self, token: &str, intent: &IntentVerification) -> bool;
+
+    /// 处理推送的内容，token对应的订阅不存在时返回false
+    ///
+    /// 签名无效的内容也应返回true，推送中心只需要知道内容已送达
+    async fn deliver(
+        &self,
+        token: &str,
+        signature: Option<&str>,
+        content_type: Option<&str>,
+        content: Vec<u8>,
+    ) -> bool;
+}
+
+/// 回调服务器收到的HTTP请求
+struct CallbackRequest {
+    method: String,
+    path: String,
+    query: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// 运行回调服务器，接受推送中心的订阅确认和内容推送
+pub async fn serve(listener: TcpListener, handler: Arc<dyn CallbackHandler>) {
+    loop {
+        let stream = match listener.accept().await {
+            Ok((stream, _)) => stream,
+            Err(e) => {
+                eprintln!("WebSub回调服务器接受连接失败: {}", e);
+                tokio::time::sleep(Duration::from_secs(1)).await;
+                continue;
+            }
+        };
+
+        let handler = handler.clone();
+        tokio::spawn(async move {
+            match timeout(
+                CONNECTION_TIMEOUT,
+                handle_connection(stream, handler.as_ref()),
+            )
+            .await
+            {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => eprintln!("WebSub回调请求处理失败: {}", e),
+                Err(_) => eprintln!("WebSub回调请求超时"),
+            }
+        });
+    }
+}
+
+/// 处理单个连接，每个连接只处理一个请求
+async fn handle_connection(
+    stream: TcpStream,
+    handler: &dyn CallbackHandler,
+) -> std::io::Result<()> {
+    let mut reader = BufReader::new(stream);
+    let (status, body) = match read_request(&mut reader).await? {
+        Ok(request) => route(request, handler).await,
+        Err(status) => (status, String::new()),
+    };
+
+    let reason = match status {
+        200 => "OK",
+        202 => "Accepted",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        _ => "Error",
+    };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    );
+    let stream = reader.get_mut();
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// 按路径和方法分发请求，返回状态码和响应内容
+async fn route(request: CallbackRequest, handler: &dyn CallbackHandler) -> (u16, String) {
+    let Some(token) = request
+        .path
+        .strip_prefix(CALLBACK_PATH)
+        .filter(|token| !token.is_empty() && !token.contains('/'))
+    else {
+        return (404, String::new());
+    };
+
+    match request.method.as_str() {
+        // 订阅意图确认
+        "GET" => {
+            let (Some(mode), Some(topic)) = (
+                request.query.get("hub.mode"),
+                request.query.get("hub.topic"),
+            ) else {
+                return (400, String::new());
+            };
+            let intent = IntentVerification {
+                mode: mode.clone(),
+                topic: topic.clone(),
+                challenge: request.query.get("hub.challenge").cloned(),
+                lease_seconds: request
+                    .query
+                    .get("hub.lease_seconds")
+                    .and_then(|seconds| seconds.trim().parse().ok()),
+                reason: request.query.get("hub.reason").cloned(),
+            };
+            if handler.verify_intent(token, &intent).await {
+                (200, intent.challenge.unwrap_or_default())
+            } else {
+                (404, String::new())
+            }
+        }
+        // 内容推送
+        "POST" => {
+            let signature = request.headers.get("x-hub-signature").map(String::as_str);
+            let content_type = request.headers.get("content-type").map(String::as_str);
+            if handler
+                .deliver(token, signature, content_type, request.body)
+                .await
+            {
+                (202, String::new())
+            } else {
+                (404, String::new())
+            }
+        }
+        _ => (405, String::new()),
+    }
+}
+
+/// 读取HTTP请求，格式错误或内容过大时返回对应的状态码
+async fn read_request<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> std::io::Result<Result<CallbackRequest, u16>> {
+    // 读取请求行和请求头
+    let mut lines = Vec::new();
+    let mut header_size = 0;
+    loop {
+        // 限制读取长度，避免过长的请求头占用内存
+        let mut line = String::new();
+        let remaining = (MAX_HEADER_SIZE - header_size) as u64;
+        let read = (&mut *reader).take(remaining).read_line(&mut line).await?;
+        header_size += read;
+        if !line.ends_with('\n') {
+            return Ok(Err(400));
+        }
+        let line = line.trim_end_matches(['\r', '\n']).to_string();
+        if line.is_empty() {
+            break;
+        }
+        lines.push(line);
+    }
+
+    let Some(request_line) = lines.first() else {
+        return Ok(Err(400));
+    };
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(400));
+    };
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let query = url::form_urlencoded::parse(query.as_bytes())
+        .into_owned()
+        .collect();
+    let headers: HashMap<String, String> = lines[1..]
+        .iter()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    // 读取请求体，支持Content-Length和分块传输
+    let chunked = headers
+        .get("transfer-encoding")
+        .is_some_and(|encoding| encoding.to_ascii_lowercase().contains("chunked"));
+    let body = if chunked {
+        match read_chunked_body(reader).await? {
+            Ok(body) => body,
+            Err(status) => return Ok(Err(status)),
+        }
+    } else {
+        let length: usize = match headers.get("content-length") {
+            Some(length) => match length.parse() {
+                Ok(length) => length,
+                Err(_) => return Ok(Err(400)),
+            },
+            None => 0,
+        };
+        if length > MAX_BODY_SIZE {
+            return Ok(Err(413));
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await?;
+        body
+    };
+
+    Ok(Ok(CallbackRequest {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        query,
+        headers,
+        body,
+    }))
+}
+
+/// 读取分块传输的请求体，分块格式错误时返回400，超过大小上限时返回413
+async fn read_chunked_body<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> std::io::Result<Result<Vec<u8>, u16>> {
+    let mut body = Vec::new();
+    loop {
+        let mut line = String::new();
+        (&mut *reader).take(1024).read_line(&mut line).await?;
+        // 忽略分块扩展参数
+        let size = line.trim().split(';').next().unwrap_or("");
+        let Ok(size) = usize::from_str_radix(size, 16) else {
+            return Ok(Err(400));
+        };
+        if size == 0 {
+            // 读取结尾的空行（忽略trailer）
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+                    break;
+                }
+            }
+            return Ok(Ok(body));
+        }
+        // 分块大小来自请求内容，检查溢出
+        if body
+            .len()
+            .checked_add(size)
+            .is_none_or(|length| length > MAX_BODY_SIZE)
+        {
+            return Ok(Err(413));
+        }
+
+        let start = body.len();
+        body.resize(start + size, 0);
+        reader.read_exact(&mut body[start..]).await?;
+        // 每个分块后的\r\n
+        let mut line_end = String::new();
+        reader.read_line(&mut line_end).await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// 记录收到的请求，只接受token为"known"的订阅
+    #[derive(Default)]
+    struct RecordingHandler {
+        intents: Mutex<Vec<IntentVerification>>,
+        deliveries: Mutex<Vec<(Option<String>, Vec<u8>)>>,
+    }
+
+    #[async_trait]
+    impl CallbackHandler for RecordingHandler {
+        async fn verify_intent(&self, token: &str, intent: &IntentVerification) -> bool {
+            self.intents.lock().unwrap().push(intent.clone());
+            token == "known"
+        }
+
+        async fn deliver(
+            &self,
+            token: &str,
+            signature: Option<&str>,
+            _content_type: Option<&str>,
+            content: Vec<u8>,
+        ) -> bool {
+            self.deliveries
+                .lock()
+                .unwrap()
+                .push((signature.map(str::to_string), content));
+            token == "known"
+        }
+    }
+
+    fn hmac_hex<M: Mac + KeyInit>(secret: &str, content: &[u8]) -> String {
+        let mut mac = <M as KeyInit>::new_from_slice(secret.as_bytes()).unwrap();
+        mac.update(content);
+        mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    async fn parse(raw: &[u8]) -> Result<CallbackRequest, u16> {
+        let mut reader = raw;
+        read_request(&mut reader).await.unwrap()
+    }
+
+    #[test]
+    fn decode_hex_accepts_only_even_length_hex() {
+        assert_eq!(decode_hex("00ff7A"), Some(vec![0x00, 0xff, 0x7a]));
+        assert_eq!(decode_hex(""), Some(vec![]));
+        assert_eq!(decode_hex("abc"), None);
+        assert_eq!(decode_hex("zz"), None);
+        assert_eq!(decode_hex("+1"), None);
+        // 多字节字符不能在字符中间切分
+        assert_eq!(decode_hex("é0"), None);
+    }
+
+    #[test]
+    fn verify_signature_supports_all_methods() {
+        let content = b"<feed/>";
+        for (method, hex) in [
+            ("sha1", hmac_hex::<Hmac<Sha1>>("secret", content)),
+            ("sha256", hmac_hex::<Hmac<Sha256>>("secret", content)),
+            ("sha384", hmac_hex::<Hmac<Sha384>>("secret", content)),
+            ("sha512", hmac_hex::<Hmac<Sha512>>("secret", content)),
+        ] {
+            let signature = format!("{}={}", method, hex);
+            assert!(
+                verify_signature("secret", &signature, content),
+                "{}",
+                method
+            );
+            assert!(verify_signature(
+                "secret",
+                &signature.to_ascii_uppercase(),
+                content
+            ));
+            assert!(
+                !verify_signature("other", &signature, content),
+                "{}",
+                method
+            );
+            assert!(
+                !verify_signature("secret", &signature, b"<feed>"),
+                "{}",
+                method
+            );
+        }
+    }
+
+    #[test]
+    fn verify_signature_rejects_malformed_values() {
+        let hex = hmac_hex::<Hmac<Sha256>>("secret", b"x");
+        assert!(!verify_signature("secret", &hex, b"x"));
+        assert!(!verify_signature("secret", &format!("md5={}", hex), b"x"));
+        assert!(!verify_signature(
+            "secret",
+            &format!("sha256={}0", hex),
+            b"x"
+        ));
+        assert!(!verify_signature(
+            "secret",
+            &format!("sha256={}", &hex[..20]),
+            b"x"
+        ));
+        assert!(!verify_signature("secret", "sha256=", b"x"));
+    }
+
+    #[test]
+    fn callback_url_joins_base_and_token() {
+        assert_eq!(
+            callback_url("https://example.com/", "abc"),
+            "https://example.com/websub/abc"
+        );
+        assert_eq!(
+            callback_url("http://127.0.0.1:8849", "abc"),
+            "http://127.0.0.1:8849/websub/abc"
+        );
+    }
+
+    #[tokio::test]
+    async fn read_request_parses_query_headers_and_body() {
+        let request = parse(
+            b"POST /websub/abc?hub.mode=subscribe&hub.topic=https%3A%2F%2Fa.example%2Ffeed HTTP/1.1\r\n\
+              X-Hub-Signature: sha1=00\r\nContent-Length: 5\r\n\r\nhello",
+        )
+        .await
+        .unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/websub/abc");
+        assert_eq!(request.query["hub.topic"], "https://a.example/feed");
+        assert_eq!(request.headers["x-hub-signature"], "sha1=00");
+        assert_eq!(request.body, b"hello");
+    }
+
+    #[tokio::test]
+    async fn read_request_rejects_oversized_headers() {
+        let mut raw = b"GET /websub/abc HTTP/1.1\r\nX-Long: ".to_vec();
+        raw.extend(std::iter::repeat_n(b'a', MAX_HEADER_SIZE));
+        raw.extend(b"\r\n\r\n");
+        assert_eq!(parse(&raw).await.err(), Some(400));
+    }
+
+    #[tokio::test]
+    async fn read_request_rejects_bad_content_length() {
+        let raw = b"POST /websub/abc HTTP/1.1\r\nContent-Length: abc\r\n\r\n";
+        assert_eq!(parse(raw).await.err(), Some(400));
+        let raw = b"POST /websub/abc HTTP/1.1\r\nContent-Length: -1\r\n\r\n";
+        assert_eq!(parse(raw).await.err(), Some(400));
+        let raw = format!(
+            "POST /websub/abc HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_SIZE + 1
+        );
+        assert_eq!(parse(raw.as_bytes()).await.err(), Some(413));
+    }
+
+    #[tokio::test]
+    async fn read_request_reads_chunked_body() {
+        let raw = b"POST /websub/abc HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+                    3;ext=1\r\n<fe\r\n4\r\ned/>\r\n0\r\nTrailer: x\r\n\r\n";
+        assert_eq!(parse(raw).await.unwrap().body, b"<feed/>");
+    }
+
+    #[tokio::test]
+    async fn read_request_rejects_bad_chunks() {
+        let raw = b"POST /websub/abc HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\nzz\r\n";
+        assert_eq!(parse(raw).await.err(), Some(400));
+        // 分块大小溢出或超过上限
+        let raw = b"POST /websub/abc HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n\
+                    1\r\na\r\nffffffffffffffff\r\n";
+        assert_eq!(parse(raw).await.err(), Some(413));
+        let raw = format!(
+            "POST /websub/abc HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n{:x}\r\n",
+            MAX_BODY_SIZE + 1
+        );
+        assert_eq!(parse(raw.as_bytes()).await.err(), Some(413));
+    }
+
+    #[tokio::test]
+    async fn route_answers_intent_verification() {
+        let handler = RecordingHandler::default();
+        let request = parse(
+            b"GET /websub/known?hub.mode=subscribe&hub.topic=t&hub.challenge=xyz&hub.lease_seconds=86400 HTTP/1.1\r\n\r\n",
+        )
+        .await
+        .unwrap();
+        assert_eq!(route(request, &handler).await, (200, "xyz".to_string()));
+        let intent = handler.intents.lock().unwrap()[0].clone();
+        assert_eq!(intent.mode, "subscribe");
+        assert_eq!(intent.lease_seconds, Some(86400));
+
+        // 未知的订阅和缺少参数的请求
+        let request = parse(
+            b"GET /websub/unknown?hub.mode=subscribe&hub.topic=t&hub.challenge=xyz HTTP/1.1\r\n\r\n",
+        )
+        .await
+        .unwrap();
+        assert_eq!(route(request, &handler).await.0, 404);
+        let request = parse(b"GET /websub/known?hub.mode=subscribe HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        assert_eq!(route(request, &handler).await.0, 400);
+    }
+
+    #[tokio::test]
+    async fn route_delivers_content() {
+        let handler = RecordingHandler::default();
+        let request = parse(
+            b"POST /websub/known HTTP/1.1\r\nX-Hub-Signature: sha1=00\r\nContent-Length: 2\r\n\r\nok",
+        )
+        .await
+        .unwrap();
+        assert_eq!(route(request, &handler).await.0, 202);
+        assert_eq!(
+            handler.deliveries.lock().unwrap()[0],
+            (Some("sha1=00".to_string()), b"ok".to_vec())
+        );
+
+        let request = parse(b"POST /websub/unknown HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+        assert_eq!(route(request, &handler).await.0, 404);
+    }
+
+    #[tokio::test]
+    async fn route_rejects_other_paths_and_methods() {
+        let handler = RecordingHandler::default();
+        for raw in [
+            &b"GET /other/known HTTP/1.1\r\n\r\n"[..],
+            b"GET /websub/ HTTP/1.1\r\n\r\n",
+            b"GET /websub/known/extra HTTP/1.1\r\n\r\n",
+        ] {
+            let request = parse(raw).await.unwrap();
+            assert_eq!(route(request, &handler).await.0, 404);
+        }
+        let request = parse(b"PUT /websub/known HTTP/1.1\r\n\r\n").await.unwrap();
+        assert_eq!(route(request, &handler).await.0, 405);
+    }
+}
@@ -73,6 +73,42 @@ let isLoading = false;
 let hasMore = true;
 let isSearching = false;
 
+// 将订阅源的请求设置填充到编辑表单
+function fillRequestOptionsForm(options) {
+  options = options || {};
+  const headers = options.headers || {};
+  document.getElementById('edit-feed-headers').value = Object.entries(headers)
+    .map(([name, value]) => `${name}: ${value}`)
+    .join('\n');
+  document.getElementById('edit-feed-cookie').value = options.cookie || '';
+  document.getElementById('edit-feed-username').value = options.username || '';
+  document.getElementById('edit-feed-password').value = options.password || '';
+  document.getElementById('edit-feed-bearer-token').value = options.bearer_token || '';
+  document.getElementById('edit-feed-user-agent').value = options.user_agent || '';
+  document.getElementById('edit-feed-bypass-proxy').checked = options.bypass_proxy || false;
+}
+
+// 从编辑表单读取请求设置，空值保存为null
+function readRequestOptionsForm() {
+  const headers = {};
+  document.getElementById('edit-feed-headers').value.split('\n').forEach(line => {
+    const index = line.indexOf(':');
+    if (index > 0) {
+      headers[line.slice(0, index).trim()] = line.slice(index + 1).trim();
+    }
+  });
+  const valueOrNull = id => document.getElementById(id).value.trim() || null;
+  return {
+    headers,
+    cookie: valueOrNull('edit-feed-cookie'),
+    username: valueOrNull('edit-feed-username'),
+    password: document.getElementById('edit-feed-password').value || null,
+    bearer_token: valueOrNull('edit-feed-bearer-token'),
+    user_agent: valueOrNull('edit-feed-user-agent'),
+    bypass_proxy: document.getElementById('edit-feed-bypass-proxy').checked
+  };
+}
+
 // 加载分组列表到下拉选择框
 async function loadGroupsToSelect(selectId) {
   try {
@@ -258,6 +294,8 @@ function initEventListeners() {
       try {
         const translateEnabled = document.getElementById('translate-enabled').checked;
         const notificationEnabled = document.getElementById('notification-enabled').checked;
+        const backfill = document.getElementById('backfill-enabled').checked;
+        const validate = document.getElementById('validate-enabled').checked;
       
       const feed = {
           id: 0, // 数据库自动生成
@@ -272,7 +310,7 @@ function initEventListeners() {
           next_retry_time: null
         };
         
-        await invoke('add_feed', { feed });
+        await invoke('add_feed', { feed, validate, backfill });
         addFeedModal.classList.remove('show');
         addFeedForm.reset();
         await loadFeeds(); // 重新加载RSS源列表
@@ -284,7 +322,7 @@ function initEventListeners() {
         // 不立即重新加载文章列表，等待后台更新完成
       } catch (error) {
         console.error('Failed to add feed:', error);
-        alert('添加RSS源失败: ' + error);
+        alert('添加RSS源失败: ' + (error.message || error));
       }
     });
   }
@@ -312,7 +350,7 @@ function initEventListeners() {
         await loadFilteredArticles(); // 重新加载文章列表
       } catch (error) {
         console.error('Failed to refresh feeds:', error);
-        alert('刷新失败: ' + error);
+        alert('刷新失败: ' + (error.message || error));
       } finally {
         refreshBtn.disabled = false;
         refreshBtn.textContent = '🔄';
@@ -902,6 +940,10 @@ function initEventListeners() {
       try {
         const translateEnabled = document.getElementById('edit-translate-enabled').checked;
         const notificationEnabled = document.getElementById('edit-notification-enabled').checked;
+        const updateIntervalMinutes = parseInt(document.getElementById('edit-feed-update-interval').value);
+        const alwaysUseBrowser = document.getElementById('edit-always-use-browser').checked;
+        const fetchFullContent = document.getElementById('edit-fetch-full-content').checked;
+        const requestOptions = readRequestOptionsForm();
       
       const feed = {
           id: feedId,
@@ -913,7 +955,11 @@ function initEventListeners() {
           notification_enabled: notificationEnabled,
           last_update_status: null,
           update_attempts: 0,
-          next_retry_time: null
+          next_retry_time: null,
+          update_interval: updateIntervalMinutes > 0 ? updateIntervalMinutes * 60 : null,
+          always_use_browser: alwaysUseBrowser,
+          fetch_full_content: fetchFullContent,
+          request_options: requestOptions
         };
         
         await invoke('update_feed', { feed });
@@ -1292,6 +1338,10 @@ async function loadFeeds() {
             document.getElementById('edit-feed-group').value = feed.group_id || '';
             document.getElementById('edit-translate-enabled').checked = feed.translate_enabled || false;
             document.getElementById('edit-notification-enabled').checked = feed.notification_enabled !== false;
+            document.getElementById('edit-feed-update-interval').value = feed.update_interval ? Math.round(feed.update_interval / 60) : '';
+            document.getElementById('edit-always-use-browser').checked = feed.always_use_browser || false;
+            document.getElementById('edit-fetch-full-content').checked = feed.fetch_full_content || false;
+            fillRequestOptionsForm(feed.request_options);
             editFeedModal.classList.add('show');
           });
         }
@@ -1455,6 +1505,10 @@ async function loadFeeds() {
             document.getElementById('edit-feed-group').value = feed.group_id || '';
             document.getElementById('edit-translate-enabled').checked = feed.translate_enabled || false;
             document.getElementById('edit-notification-enabled').checked = feed.notification_enabled !== false;
+            document.getElementById('edit-feed-update-interval').value = feed.update_interval ? Math.round(feed.update_interval / 60) : '';
+            document.getElementById('edit-always-use-browser').checked = feed.always_use_browser || false;
+            document.getElementById('edit-fetch-full-content').checked = feed.fetch_full_content || false;
+            fillRequestOptionsForm(feed.request_options);
             editFeedModal.classList.add('show');
           });
         }
@@ -1697,6 +1751,121 @@ async function loadArticles(feedId = null, page = 1, size = pageSize) {
   }
 }
 
+// 格式化媒体时长（秒）为 时:分:秒
+function formatDuration(seconds) {
+  const h = Math.floor(seconds / 3600);
+  const m = Math.floor((seconds % 3600) / 60);
+  const s = seconds % 60;
+  const pad = (n) => String(n).padStart(2, '0');
+  return h > 0 ? `${h}:${pad(m)}:${pad(s)}` : `${m}:${pad(s)}`;
+}
+
+// 显示文章内容，带有音频、视频附件（播客节目）时在正文前显示播放器
+function renderArticleBody(article, contentToShow) {
+  const articleBody = document.getElementById('article-body');
+  articleBody.innerHTML = contentToShow;
+
+  const enclosures = article.enclosures || [];
+  if (enclosures.length === 0) {
+    return;
+  }
+  const container = document.createElement('div');
+  container.className = 'article-enclosures';
+  for (const enclosure of enclosures) {
+    const mimeType = enclosure.mime_type || '';
+    const item = document.createElement('div');
+    item.className = 'article-enclosure';
+
+    const info = document.createElement('div');
+    info.className = 'article-enclosure-info';
+    const parts = [];
+    if (enclosure.season != null) parts.push(`第${enclosure.season}季`);
+    if (enclosure.episode != null) parts.push(`第${enclosure.episode}集`);
+    if (enclosure.duration != null) parts.push(`时长 ${formatDuration(enclosure.duration)}`);
+    if (enclosure.length) parts.push(`${(enclosure.length / 1024 / 1024).toFixed(1)} MB`);
+    info.textContent = parts.join(' · ');
+
+    if (mimeType.startsWith('audio/') || mimeType.startsWith('video/')) {
+      const player = document.createElement(mimeType.startsWith('video/') ? 'video' : 'audio');
+      player.controls = true;
+      player.preload = 'none';
+      player.src = enclosure.url;
+      if (enclosure.image && player.tagName === 'VIDEO') {
+        player.poster = enclosure.image;
+      }
+      item.appendChild(player);
+    } else {
+      // 无法播放的附件显示为下载链接
+      const link = document.createElement('a');
+      link.href = '#';
+      link.textContent = enclosure.url.split('/').pop() || enclosure.url;
+      link.onclick = async (e) => {
+        e.preventDefault();
+        await invoke('open_link', { url: enclosure.url });
+      };
+      item.appendChild(link);
+    }
+    if (parts.length > 0) {
+      item.appendChild(info);
+    }
+    container.appendChild(item);
+  }
+  articleBody.prepend(container);
+}
+
+// 文章被发布者修改过时，在文章信息中显示"查看修改"按钮
+async function showArticleRevisions(article, contentToShow) {
+  const meta = document.getElementById('article-meta');
+  meta.dataset.articleId = article.id;
+  try {
+    const revisions = await invoke('get_article_revisions', { articleId: article.id });
+    // 等待期间用户可能已切换到其他文章
+    if (revisions.length === 0 || meta.dataset.articleId !== String(article.id)) {
+      return;
+    }
+
+    const updatedSpan = document.createElement('span');
+    updatedSpan.textContent = `已修改 ${revisions.length} 次`;
+    if (article.updated) {
+      updatedSpan.textContent += `，最后修改: ${new Date(article.updated).toLocaleString()}`;
+    }
+    const diffBtn = document.createElement('button');
+    diffBtn.className = 'revision-diff-btn';
+    diffBtn.textContent = '查看修改';
+    meta.appendChild(updatedSpan);
+    meta.appendChild(diffBtn);
+
+    let showingDiff = false;
+    diffBtn.onclick = async () => {
+      const articleBody = document.getElementById('article-body');
+      if (showingDiff) {
+        renderArticleBody(article, contentToShow);
+        diffBtn.textContent = '查看修改';
+        showingDiff = false;
+        return;
+      }
+      try {
+        const diff = await invoke('get_article_diff', { articleId: article.id });
+        articleBody.innerHTML = '';
+        // 按段落显示差异，删除的内容标红，增加的内容标绿
+        for (const line of [...diff.title, ...diff.content]) {
+          const paragraph = document.createElement('p');
+          paragraph.className = `diff-line diff-${line.kind}`;
+          paragraph.textContent = line.text;
+          articleBody.appendChild(paragraph);
+        }
+        diffBtn.textContent = '查看当前内容';
+        showingDiff = true;
+      } catch (error) {
+        console.error('获取文章修改失败:', error);
+        alert('获取文章修改失败: ' + error);
+      }
+    };
+  } catch (error) {
+    console.error('获取文章历史版本失败:', error);
+  }
+}
+
 // 加载文章内容
 async function loadArticleContent(article) {
   try {
@@ -1707,7 +1876,7 @@ async function loadArticleContent(article) {
   titleToShow = titleToShow.replace(/[\r\n]+/g, ' ').trim();
   const contentToShow = article.translated_content || article.content;
   document.getElementById('article-title').textContent = titleToShow;
-  document.getElementById('article-body').innerHTML = contentToShow;
+  renderArticleBody(article, contentToShow);
   // 获取订阅源名称
   const feedName = feedMap.get(article.feed_id) || '未知来源';
   document.getElementById('article-meta').innerHTML = `
@@ -1715,6 +1884,7 @@ async function loadArticleContent(article) {
     <span>发布时间: ${new Date(article.pub_date).toLocaleString()}</span>
     <span>来源: ${feedName}</span>
   `;
+  showArticleRevisions(article, contentToShow);
     
     // 更新文章操作按钮状态
     const markReadBtn = document.getElementById('mark-read-btn');
@@ -2260,6 +2430,16 @@ window.addEventListener('DOMContentLoaded', async () => {
     loadFilteredArticles();
   });
   
+  // 监听历史文章回溯进度
+  await listen('backfill_progress', (event) => {
+    const { pages, articles, done } = event.payload;
+    if (done) {
+      showNotification(`历史文章获取完成：共 ${pages} 页，${articles} 篇文章`, 'success');
+    } else {
+      showNotification(`正在获取历史文章：第 ${pages} 页，已获取 ${articles} 篇`, 'info', 1500);
+    }
+  });
+  
   // 监听AI聊天响应事件
   await listen('ai_chat_response', async (event) => {
     await handleChatResponse(event.payload);